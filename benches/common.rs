@@ -1,6 +1,7 @@
 use std::io::{BufReader, BufRead};
 use std::fs::File;
 use sc2pathlib::path_find;
+use sc2pathlib::npy;
 
 
 fn rot90(vec: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
@@ -38,4 +39,14 @@ fn read_vec_from_file(file_path: &str) -> Vec<Vec<usize>> {
 pub fn get_pathfind(file: &str) -> path_find::PathFind {
     let map = read_vec_from_file(file);
     path_find::PathFind::new_internal(map)
-}
\ No newline at end of file
+}
+
+type Grid = Vec<Vec<usize>>;
+
+/// Grids of `tests/<name>_*.npy` in pathing, placement, height order.
+pub fn get_npy_grids(name: &str) -> (Grid, Grid, Grid) {
+    let pathing = npy::load_grid(format!("tests/{}_pathing.npy", name)).unwrap();
+    let placement = npy::load_grid(format!("tests/{}_placement.npy", name)).unwrap();
+    let height = npy::load_grid(format!("tests/{}_height.npy", name)).unwrap();
+    (pathing, placement, height)
+}
@@ -1,5 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use common::get_pathfind;
+use common::{get_npy_grids, get_pathfind};
+use sc2pathlib::mapping::map::Map;
 mod common;
 
 fn bench_astar_automaton(c: &mut Criterion) {
@@ -31,5 +32,14 @@ fn bench_astar_10x10(c: &mut Criterion) {
      })});
 }
 
-criterion_group!(benches, bench_astar_automaton, bench_astar_4x4, bench_astar_10x10);
+fn bench_create_map_automaton(c: &mut Criterion) {
+    let (pathing, placement, height) = get_npy_grids("AutomatonLE");
+    c.bench_function("create_map_automaton", |b| {
+        b.iter(|| {
+            Map::new(pathing.clone(), placement.clone(), height.clone(), 18, 16, 166, 164).unwrap();
+        })
+    });
+}
+
+criterion_group!(benches, bench_astar_automaton, bench_astar_4x4, bench_astar_10x10, bench_create_map_automaton);
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-cargo build --release
+cargo build --release --features python
 REM if not exist "sc2pathlib" mkdir sc2pathlib
 copy "target\release\sc2pathlib.dll" "sc2pathlibp\sc2pathlib.pyd"
 pause
\ No newline at end of file
@@ -0,0 +1,25 @@
+// Regenerates the C header of the `capi` feature into `include/sc2pathlib.h` when `SC2PATHLIB_UPDATE_HEADER`
+// is set, so that builds don't write into the source tree
+fn main() {
+    #[cfg(feature = "capi")]
+    generate_header();
+}
+
+#[cfg(feature = "capi")]
+fn generate_header() {
+    println!("cargo:rerun-if-env-changed=SC2PATHLIB_UPDATE_HEADER");
+    if std::env::var_os("SC2PATHLIB_UPDATE_HEADER").is_none() {
+        return;
+    }
+
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+    cbindgen::Builder::new().with_crate(&crate_dir)
+                            .with_config(config)
+                            .generate()
+                            .expect("Unable to generate C header")
+                            .write_to_file(format!("{}/include/sc2pathlib.h", crate_dir));
+}
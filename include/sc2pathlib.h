@@ -0,0 +1,251 @@
+#ifndef SC2PATHLIB_H
+#define SC2PATHLIB_H
+
+/* Generated by cbindgen from src/capi.rs, do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Mapping for python-sc2
+ */
+typedef struct Map Map;
+
+typedef struct PathFind PathFind;
+
+/**
+ * Grid cell.
+ */
+typedef struct Sc2Point {
+  size_t x;
+  size_t y;
+} Sc2Point;
+
+/**
+ * Position in world coordinates.
+ */
+typedef struct Sc2PointF {
+  float x;
+  float y;
+} Sc2PointF;
+
+/**
+ * Summary of a choke, the cells are available through `sc2pathlib_map_choke_pixels`.
+ */
+typedef struct Sc2Choke {
+  struct Sc2PointF main_line_start;
+  struct Sc2PointF main_line_end;
+  float min_length;
+  size_t pixel_count;
+} Sc2Choke;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a pathfinder from a `width * height` grid where 0 is blocked, returns null on invalid input.
+ */
+struct PathFind *sc2pathlib_pathfind_new(const size_t *grid,
+                                         size_t width,
+                                         size_t height);
+
+void sc2pathlib_pathfind_free(struct PathFind *path_find);
+
+size_t sc2pathlib_pathfind_width(const struct PathFind *path_find);
+
+size_t sc2pathlib_pathfind_height(const struct PathFind *path_find);
+
+/**
+ * Value of the cell, 0 when blocked or out of bounds.
+ */
+size_t sc2pathlib_pathfind_get(const struct PathFind *path_find, struct Sc2Point point);
+
+/**
+ * Restores the grid the pathfinder was created with.
+ */
+void sc2pathlib_pathfind_reset(struct PathFind *path_find);
+
+void sc2pathlib_pathfind_create_block(struct PathFind *path_find,
+                                      struct Sc2PointF center,
+                                      size_t width,
+                                      size_t height);
+
+void sc2pathlib_pathfind_remove_block(struct PathFind *path_find,
+                                      struct Sc2PointF center,
+                                      size_t width,
+                                      size_t height);
+
+void sc2pathlib_pathfind_normalize_influence(struct PathFind *path_find, size_t value);
+
+/**
+ * Adds influence fading with euclidean distance around each position.
+ */
+void sc2pathlib_pathfind_add_influence(struct PathFind *path_find,
+                                       const struct Sc2Point *positions,
+                                       size_t count,
+                                       float max,
+                                       float distance);
+
+/**
+ * Adds the same influence to every cell within euclidean distance of each position.
+ */
+void sc2pathlib_pathfind_add_influence_flat(struct PathFind *path_find,
+                                            const struct Sc2Point *positions,
+                                            size_t count,
+                                            float max,
+                                            float distance);
+
+/**
+ * Adds influence fading with walking distance around each position.
+ */
+void sc2pathlib_pathfind_add_walk_influence(struct PathFind *path_find,
+                                            const struct Sc2Point *positions,
+                                            size_t count,
+                                            float max,
+                                            float distance);
+
+/**
+ * Shortest path ignoring influence, `heuristic` below 0 selects the default heuristic.
+ */
+size_t sc2pathlib_pathfind_find_path(const struct PathFind *path_find,
+                                     struct Sc2Point start,
+                                     struct Sc2Point end,
+                                     int32_t heuristic_type,
+                                     struct Sc2Point *out_path,
+                                     size_t capacity,
+                                     float *out_distance);
+
+/**
+ * Shortest path ignoring influence for units larger than a single cell.
+ */
+size_t sc2pathlib_pathfind_find_path_large(const struct PathFind *path_find,
+                                           struct Sc2Point start,
+                                           struct Sc2Point end,
+                                           int32_t heuristic_type,
+                                           struct Sc2Point *out_path,
+                                           size_t capacity,
+                                           float *out_distance);
+
+/**
+ * Path using influence values as cost.
+ */
+size_t sc2pathlib_pathfind_find_path_influence(const struct PathFind *path_find,
+                                               struct Sc2Point start,
+                                               struct Sc2Point end,
+                                               int32_t heuristic_type,
+                                               struct Sc2Point *out_path,
+                                               size_t capacity,
+                                               float *out_distance);
+
+/**
+ * Analyses the map from `width * height` grids as sent by the game, returns null on invalid input.
+ */
+struct Map *sc2pathlib_map_new(const uint8_t *pathing,
+                               const uint8_t *placement,
+                               const uint8_t *height_map,
+                               size_t width,
+                               size_t height,
+                               size_t x_start,
+                               size_t y_start,
+                               size_t x_end,
+                               size_t y_end);
+
+void sc2pathlib_map_free(struct Map *map);
+
+/**
+ * Pathing layer of the map, `map_type` uses the values of `MapType`. The pointer is owned by the map.
+ */
+struct PathFind *sc2pathlib_map_pathing(struct Map *map,
+                                        uint8_t map_type);
+
+/**
+ * Reset all mapping to their originals.
+ */
+void sc2pathlib_map_reset(struct Map *map);
+
+/**
+ * Blocks the area on all ground layers.
+ */
+void sc2pathlib_map_create_block(struct Map *map,
+                                 struct Sc2PointF center,
+                                 size_t width,
+                                 size_t height);
+
+void sc2pathlib_map_remove_block(struct Map *map,
+                                 struct Sc2PointF center,
+                                 size_t width,
+                                 size_t height);
+
+void sc2pathlib_map_normalize_influence(struct Map *map, size_t value);
+
+/**
+ * Adds influence fading with walking distance to the ground layers.
+ */
+void sc2pathlib_map_add_influence_walk(struct Map *map,
+                                       const struct Sc2PointF *positions,
+                                       size_t count,
+                                       float influence,
+                                       float distance);
+
+/**
+ * Adds full influence up to `min` fading out to `max`, `maps_type` uses the values of `MapsType`.
+ */
+void sc2pathlib_map_add_influence_fading(struct Map *map,
+                                         size_t maps_type,
+                                         const struct Sc2PointF *positions,
+                                         size_t count,
+                                         float influence,
+                                         float min,
+                                         float max);
+
+/**
+ * Shortest path on the selected layer ignoring influence.
+ */
+size_t sc2pathlib_map_find_path(const struct Map *map,
+                                uint8_t map_type,
+                                struct Sc2PointF start,
+                                struct Sc2PointF end,
+                                int32_t heuristic_type,
+                                struct Sc2Point *out_path,
+                                size_t capacity,
+                                float *out_distance);
+
+/**
+ * Path on the selected layer using influence values as cost.
+ */
+size_t sc2pathlib_map_find_path_influence(const struct Map *map,
+                                          uint8_t map_type,
+                                          struct Sc2PointF start,
+                                          struct Sc2PointF end,
+                                          int32_t heuristic_type,
+                                          struct Sc2Point *out_path,
+                                          size_t capacity,
+                                          float *out_distance);
+
+/**
+ * Writes the overlord spots into `out` and returns their count.
+ */
+size_t sc2pathlib_map_overlord_spots(const struct Map *map, struct Sc2PointF *out, size_t capacity);
+
+size_t sc2pathlib_map_choke_count(const struct Map *map);
+
+/**
+ * Fills `out` with the choke at `index`, returns false when the index is out of range.
+ */
+bool sc2pathlib_map_choke(const struct Map *map, size_t index, struct Sc2Choke *out);
+
+/**
+ * Writes the cells of the choke at `index` into `out` and returns their count.
+ */
+size_t sc2pathlib_map_choke_pixels(const struct Map *map,
+                                   size_t index,
+                                   struct Sc2Point *out,
+                                   size_t capacity);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* SC2PATHLIB_H */
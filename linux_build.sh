@@ -1,2 +1,2 @@
-cargo build --release
+cargo build --release --features python
 cp "target/release/libsc2pathlib.so" "sc2pathlibp/sc2pathlib.so"
\ No newline at end of file
@@ -0,0 +1,6 @@
+from typing import Tuple, List
+
+class Expansion:
+    center: Tuple[float, float]
+    minerals: List[Tuple[float, float]]
+    geysers: List[Tuple[float, float]]
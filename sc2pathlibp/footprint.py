@@ -0,0 +1,26 @@
+from typing import Tuple, List, Optional
+
+
+class Footprint:
+    corner: Tuple[float, float]
+    pathing: List[Tuple[int, int]]
+    placement: List[Tuple[int, int]]
+
+    def __init__(
+        self, corner: Tuple[float, float], pathing: List[Tuple[int, int]], placement: List[Tuple[int, int]]
+    ): ...
+
+    @staticmethod
+    def rectangle(size: Tuple[int, int]) -> "Footprint": ...
+
+    @staticmethod
+    def named(name: str) -> Optional["Footprint"]: ...
+
+    @staticmethod
+    def names() -> List[str]: ...
+
+
+class Obstacle:
+    id: int
+    footprint: Footprint
+    center: Tuple[float, float]
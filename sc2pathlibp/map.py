@@ -3,6 +3,11 @@ from .sc2pathlib import Map
 import numpy as np
 from typing import List, Optional, Tuple, Union
 from .choke import Choke
+from .footprint import Footprint, Obstacle
+from .ramp import Ramp
+from .reaper import ReaperJump
+from .region import Region, RegionRoute
+from .expansion import Expansion
 from .mappings import MapsType, MapType
 
 
@@ -13,23 +18,63 @@ class Sc2Map:
         placement_grid: np.ndarray,
         height_map: np.ndarray,
         playable_area: "sc2.position.Rect",
+        cache_path: Optional[str] = None,
     ):
+        """
+        :param cache_path: Optional file for storing the map analysis. A cache created from different grids is rebuilt.
+        """
 
-        self._overlord_spots: Optional[List[Tuple[float, float]]] = None
-        self._chokes: Optional[List[Choke]] = None
-        self.heuristic_accuracy = 1  # Octile distance / set to 2 for optimal accuracy but less performance
+        self._init_state(height_map)
+        area = (
+            playable_area.x,
+            playable_area.y,
+            playable_area.x + playable_area.width,
+            playable_area.y + playable_area.height,
+        )
 
-        self.height_map = height_map
-        self._map = Map(
-            np.swapaxes(pathing_grid, 0, 1),
-            np.swapaxes(placement_grid, 0, 1),
-            np.swapaxes(height_map, 0, 1),
+        self._map: Optional[Map] = None
+        if cache_path is not None:
+            try:
+                expected_hash = Map.input_hash_of(pathing_grid, placement_grid, height_map, *area, "yx")
+                self._map = Map.load(cache_path, expected_hash)
+            except OSError:
+                self._map = None
+
+        if self._map is None:
+            # Game grids are indexed [y][x]
+            self._map = Map(pathing_grid, placement_grid, height_map, *area, "yx")
+            if cache_path is not None:
+                self._map.save(cache_path)
+
+    @classmethod
+    def from_game_info(cls, game_info: "sc2.game_info.GameInfo") -> "Sc2Map":
+        """
+        Creates the map from the packed grids sent by the game without unpacking them in Python.
+        """
+        start_raw = game_info._proto.start_raw
+        playable_area = game_info.playable_area
+        area = (
             playable_area.x,
             playable_area.y,
             playable_area.x + playable_area.width,
             playable_area.y + playable_area.height,
         )
 
+        sc2_map = cls.__new__(cls)
+        sc2_map._init_state(game_info.terrain_height.data_numpy)
+        sc2_map._map = Map.from_image_data(
+            start_raw.pathing_grid, start_raw.placement_grid, start_raw.terrain_height, *area
+        )
+        return sc2_map
+
+    def _init_state(self, height_map: np.ndarray):
+        self._overlord_spots: Optional[List[Tuple[float, float]]] = None
+        self._chokes: Optional[List[Choke]] = None
+        self._regions: Optional[List[Region]] = None
+        self.heuristic_accuracy = 1  # Octile distance / set to 2 for optimal accuracy but less performance
+
+        self.height_map = height_map
+
     @property
     def overlord_spots(self) -> List[Tuple[float, float]]:
         if self._overlord_spots is not None:
@@ -44,6 +89,188 @@ class Sc2Map:
         self._chokes = self._map.chokes
         return self._chokes
 
+    @property
+    def regions(self) -> List[Region]:
+        """
+        Walkable areas separated by chokes, `Region.chokes` are indices in `chokes`.
+        """
+        if self._regions is not None:
+            return self._regions
+        self._regions = self._map.regions
+        return self._regions
+
+    def region_at(self, pos: Tuple[float, float]) -> Optional[Region]:
+        return self._map.region_at(pos)
+
+    @property
+    def region_distances(self) -> List[List[float]]:
+        """
+        Ground distance between the region centers as `region_distances[start][end]`, walking through the
+        centers of the chokes on the way.
+        """
+        return self._map.region_distances
+
+    @property
+    def choke_edges(self) -> List[Tuple[int, int, float]]:
+        """
+        Ground distance between the centers of chokes that border the same region, as (first, second, distance).
+        """
+        return self._map.choke_edges
+
+    def region_route(self, start: Tuple[float, float], end: Tuple[float, float]) -> Optional[RegionRoute]:
+        """
+        Regions and chokes passed on the way from the region of start to the region of end.
+        """
+        return self._map.region_route(start, end)
+
+    @property
+    def ramps(self) -> List[Ramp]:
+        return self._map.ramps
+
+    def main_ramp(self, start_location: Tuple[float, float]) -> Optional[Ramp]:
+        """
+        Ramp leading down from the main base of the start location.
+        """
+        return self._map.main_ramp(start_location)
+
+    @property
+    def terrain_levels(self) -> np.ndarray:
+        return self._map.terrain_levels
+
+    def terrain_level(self, pos: Tuple[float, float]) -> Optional[int]:
+        return self._map.terrain_level(pos)
+
+    def world_height(self, pos: Tuple[float, float]) -> Optional[float]:
+        """
+        World z at the position, the same value as `game_info.terrain_height` converted by python-sc2.
+        """
+        return self._map.world_height(pos)
+
+    def is_high_ground_over(self, high: Tuple[float, float], low: Tuple[float, float]) -> bool:
+        return self._map.is_high_ground_over(high, low)
+
+    def overlooking_cells(self, pos: Tuple[float, float], distance: float) -> List[Tuple[int, int]]:
+        """
+        Walkable cells within distance that are on a higher terrain level than pos.
+        """
+        return self._map.overlooking_cells(pos, distance)
+
+    def overlooking_layer(self, distance: float) -> np.ndarray:
+        """
+        Number of walkable cells within distance on a higher terrain level than each cell.
+        """
+        return self._map.overlooking_layer(distance)
+
+    def can_place(self, size: Tuple[int, int], center: Tuple[float, float]) -> bool:
+        return self._map.can_place(size, center)
+
+    def find_placement_near(
+        self,
+        size: Tuple[int, int],
+        center: Tuple[float, float],
+        max_distance: float,
+        walkway: bool = False,
+        mineral_lines: Optional[List[Expansion]] = None,
+        creep: bool = False,
+    ) -> Optional[Tuple[float, float]]:
+        """
+        Closest position to center where a building of the size can be placed.
+        :param walkway: Keep the cells around the building pathable
+        :param mineral_lines: Expansions whose area between the town hall and the resources is kept free
+        :param creep: Only place on creep, for Zerg buildings
+        """
+        return self._map.find_placement_near(size, center, max_distance, walkway, mineral_lines, creep)
+
+    def find_placements(
+        self,
+        size: Tuple[int, int],
+        center: Tuple[float, float],
+        max_distance: float,
+        count: int,
+        walkway: bool = False,
+        mineral_lines: Optional[List[Expansion]] = None,
+        creep: bool = False,
+    ) -> List[Tuple[float, float]]:
+        """
+        Positions for up to count buildings of the size that don't overlap, lowest ground influence first.
+        """
+        return self._map.find_placements(size, center, max_distance, count, walkway, mineral_lines, creep)
+
+    @property
+    def creep(self) -> np.ndarray:
+        return self._map.creep
+
+    def update_creep(self, creep: "sc2.pixel_map.PixelMap"):
+        """
+        Replaces the creep layer, usually with `bot.state.creep`.
+        """
+        self._map.set_creep_image_data(creep._proto)
+
+    def on_creep(self, size: Tuple[int, int], center: Tuple[float, float]) -> bool:
+        return self._map.on_creep(size, center)
+
+    def plan_creep_tumors(
+        self, tumors: List[Tuple[float, float]], target: Tuple[float, float], max_influence: int = 0
+    ) -> List[Tuple[Tuple[float, float], Tuple[float, float]]]:
+        """
+        Next tumor position for each of the tumors that can spread, as (tumor, position) pairs.
+        :param tumors: Positions of the tumors that can spread creep
+        :param target: Where the creep should go, for example the enemy natural
+        :param max_influence: Highest ground influence above normal a position can have
+        """
+        return self._map.plan_creep_tumors(tumors, target, max_influence)
+
+    def warp_in_cells(
+        self,
+        sources: List[Tuple[Tuple[float, float], float]],
+        occupied: Optional[List[Tuple[float, float]]] = None,
+    ) -> List[Tuple[int, int]]:
+        """
+        Cells units can be warped in on.
+        :param sources: Positions and power radiuses of pylons (6.5) and phasing warp prisms (3.75)
+        :param occupied: Positions of units standing in the power field
+        """
+        return self._map.warp_in_cells(sources, occupied)
+
+    def find_warp_ins(
+        self,
+        sources: List[Tuple[Tuple[float, float], float]],
+        target: Tuple[float, float],
+        count: int,
+        spacing: float = 1.0,
+        occupied: Optional[List[Tuple[float, float]]] = None,
+    ) -> List[Tuple[float, float]]:
+        """
+        Up to count warp-in positions at least spacing apart, lowest ground influence first and then closest to target.
+        """
+        return self._map.find_warp_ins(sources, target, count, spacing, occupied)
+
+    def wall_ramp(
+        self, ramp: int, buildings: List[Tuple[int, int]], gap: int = 0
+    ) -> Optional[List[Tuple[float, float]]]:
+        """
+        Building centers, in the order of `buildings`, that close the top of `ramps[ramp]`.
+        :param buildings: Building sizes, for example [(3, 3), (2, 2), (2, 2)] for a barracks and two supply depots
+        :param gap: Size of units that can still pass the wall, 0 closes it completely
+        """
+        return self._map.wall_ramp(ramp, buildings, gap)
+
+    def wall_choke(
+        self, choke: int, buildings: List[Tuple[int, int]], gap: int = 0
+    ) -> Optional[List[Tuple[float, float]]]:
+        """
+        Building centers, in the order of `buildings`, that close `chokes[choke]` between the regions it connects.
+        """
+        return self._map.wall_choke(choke, buildings, gap)
+
+    def expansion_locations(
+        self, minerals: List[Tuple[float, float]], geysers: List[Tuple[float, float]]
+    ) -> List[Expansion]:
+        """
+        Town hall position for each group of resources, for example from `bot.mineral_field` and `bot.vespene_geyser`.
+        """
+        return self._map.expansion_locations(minerals, geysers)
+
     def reset(self):
         self._map.reset()
 
@@ -68,6 +295,22 @@ class Sc2Map:
         else:
             self._map.remove_block(center, size)
 
+    def create_footprint_block(self, shape: Union[str, Footprint], center: Tuple[float, float]):
+        self._map.create_footprint_block(shape, center)
+
+    def remove_footprint_block(self, shape: Union[str, Footprint], center: Tuple[float, float]):
+        self._map.remove_footprint_block(shape, center)
+
+    def add_obstacle(self, id: int, shape: Union[str, Footprint], center: Tuple[float, float]):
+        self._map.add_obstacle(id, shape, center)
+
+    def remove_obstacle(self, id: int) -> bool:
+        return self._map.remove_obstacle(id)
+
+    @property
+    def obstacles(self) -> List[Obstacle]:
+        return self._map.obstacles
+
     def add_walk_influence(self, points: List["sc.Point2"], influence: float, range: float = 3):
         """
         Influence applied fades up until the specified range
@@ -118,6 +361,40 @@ class Sc2Map:
             return self._map.find_path_large(map_type, start, end, self.heuristic_accuracy)
         return self._map.find_path(map_type, start, end, self.heuristic_accuracy)
 
+    def find_path_creep(
+        self, map_type: MapType, start: (float, float), end: (float, float), creep_speed: float = 1.3
+    ) -> Tuple[List[Tuple[int, int]], float]:
+        """
+        Finds the fastest path for a unit that moves creep_speed times faster on creep.
+
+        :return: Tuple of points and the distance the unit would cover off creep in the same time.
+        """
+        return self._map.find_path_creep(map_type, start, end, creep_speed)
+
+    @property
+    def reaper_jumps(self) -> List[ReaperJump]:
+        return self._map.reaper_jumps
+
+    def find_path_reaper(
+        self, start: (float, float), end: (float, float), jump_cost: float = 2.0
+    ) -> Tuple[List[Tuple[int, int]], float, List[ReaperJump]]:
+        """
+        Finds a path for a reaper that jumps over cliffs where it is faster.
+
+        :param jump_cost: Time a jump takes as walking distance
+        :return: Tuple of points, distance and the jumps along the path.
+        """
+        return self._map.find_path_reaper(start, end, jump_cost)
+
+    def reaper_escape_jump(
+        self, start: (float, float), max_influence: int = 0
+    ) -> Optional[Tuple[ReaperJump, float]]:
+        """
+        Closest cliff jump that lands outside of influence, with the influenced walking distance to it.
+        :param max_influence: Highest reaper influence above normal the landing can have
+        """
+        return self._map.reaper_escape_jump(start, max_influence)
+
     def find_path_influence(
         self, map_type: MapType, start: (float, float), end: (float, float), large: bool = False
     ) -> (List[Tuple[int, int]], float):
@@ -227,3 +504,32 @@ class Sc2Map:
         resized = cv2.resize(image, dsize=None, fx=resize, fy=resize, interpolation=cv2.INTER_NEAREST)
         cv2.imshow(image_name, resized)
         cv2.waitKey(1)
+
+    def save_image(self, file_name: str, resize: int = 4):
+        """
+        Saves the image shown by `plot` without requiring opencv or a display.
+
+        :param file_name: target file, format is selected by the extension: .png, .ppm or .pgm
+        :param resize: multiplier for resizing the image
+        """
+        self._map.save_climbs_image(file_name, resize)
+
+    def save_chokes_image(self, file_name: str, resize: int = 4):
+        self._map.save_chokes_image(file_name, resize)
+
+    def save_overlord_spots_image(self, file_name: str, resize: int = 4):
+        self._map.save_overlord_spots_image(file_name, resize)
+
+    def save_pathing_image(
+        self,
+        map_type: MapType,
+        file_name: str,
+        path: Optional[List[Tuple[int, int]]] = None,
+        influence: bool = False,
+        resize: int = 4,
+    ):
+        """
+        :param influence: draw the pathing values as a heatmap instead of pathable / blocked
+        """
+        paths = [path] if path else []
+        self._map.save_layer_image(map_type, file_name, paths, influence, resize)
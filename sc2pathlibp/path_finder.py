@@ -9,12 +9,14 @@ def to_float2(original: Tuple[int, int]) -> Tuple[float, float]:
 
 
 class PathFinder:
-    def __init__(self, maze: Union[List[List[int]], np.array]):
+    def __init__(self, maze: Union[List[List[int]], np.array], orientation: str = "xy"):
         """ 
         pathing values need to be integers to improve performance. 
         Initialization should be done with array consisting values of 0 and 1.
+
+        :param orientation: "xy" when maze is indexed [x][y], "yx" for [y][x] arrays received from the game
         """
-        self._path_find = PathFind(maze)
+        self._path_find = PathFind(maze, orientation)
         self.heuristic_accuracy = 1  # Octile distance
 
     def normalize_influence(self, value: int):
@@ -40,9 +42,9 @@ class PathFinder:
         return self._path_find.height
 
     @property
-    def map(self) -> List[List[int]]:
+    def map(self) -> np.ndarray:
         """
-        :return: map as list of lists [x][y] in python readable format
+        :return: map as numpy array indexed [x][y]
         """
         return self._path_find.map
 
@@ -67,6 +69,22 @@ class PathFinder:
         else:
             self._path_find.remove_block(center, size)
 
+    def create_footprint_block(self, shape: Union[str, "Footprint"], center: Tuple[float, float]):
+        self._path_find.create_footprint_block(shape, center)
+
+    def remove_footprint_block(self, shape: Union[str, "Footprint"], center: Tuple[float, float]):
+        self._path_find.remove_footprint_block(shape, center)
+
+    def add_obstacle(self, id: int, shape: Union[str, "Footprint"], center: Tuple[float, float]):
+        self._path_find.add_obstacle(id, shape, center)
+
+    def remove_obstacle(self, id: int) -> bool:
+        return self._path_find.remove_obstacle(id)
+
+    @property
+    def obstacles(self) -> List["Obstacle"]:
+        return self._path_find.obstacles
+
     def find_path(
         self, start: (float, float), end: (float, float), large: bool = False
     ) -> Tuple[List[Tuple[int, int]], float]:
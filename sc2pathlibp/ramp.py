@@ -0,0 +1,15 @@
+from typing import Tuple, List, Optional
+
+class Ramp:
+    index: int
+    cells: List[Tuple[int, int]]
+    upper_height: int
+    lower_height: int
+    upper: List[Tuple[int, int]]
+    lower: List[Tuple[int, int]]
+    top_center: Tuple[float, float]
+    bottom_center: Tuple[float, float]
+    direction: Tuple[float, float]
+    width: float
+    upper_region: Optional[int]
+    lower_region: Optional[int]
@@ -0,0 +1,6 @@
+from typing import Tuple
+
+class ReaperJump:
+    takeoff: Tuple[int, int]
+    landing: Tuple[int, int]
+    up: bool
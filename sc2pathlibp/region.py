@@ -0,0 +1,20 @@
+from typing import Tuple, List
+
+class Region:
+    index: int
+    area: int
+    centroid: Tuple[float, float]
+    bounding_box: Tuple[Tuple[int, int], Tuple[int, int]]
+    height: int
+    chokes: List[int]
+
+
+class RegionConnection:
+    choke: int
+    regions: Tuple[int, int]
+
+
+class RegionRoute:
+    regions: List[int]
+    chokes: List[int]
+    distance: float
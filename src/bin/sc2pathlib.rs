@@ -0,0 +1,323 @@
+//! Offline map analyzer, loads the grids of a map from `.npy` files and prints a JSON report.
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::MapType;
+use sc2pathlib::npy;
+use sc2pathlib::path_find::PathFind;
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+const USAGE: &str = "Usage: sc2pathlib <map prefix> [options]
+
+Loads <prefix>_pathing.npy, <prefix>_placement.npy and <prefix>_height.npy as saved by
+pull_map_data.py, analyses the map and prints a JSON report.
+
+Options:
+  --pathing <file>        pathing grid, overrides the prefix
+  --placement <file>      placement grid, overrides the prefix
+  --height <file>         height grid, overrides the prefix
+  --area <x0,y0,x1,y1>    playable area, defaults to the bounding box of walkable cells
+  --path <type:x,y:x,y>   path query on ground, reaper, colossus or air, can be repeated
+  --output <file>         write the report to a file instead of stdout
+  --images <dir>          save pathing, climb, choke and overlord spot images into the directory
+  --scale <n>             image scale, defaults to 4
+  -h, --help              show this help";
+
+#[derive(Default)]
+struct Options {
+    prefix: Option<String>,
+    pathing: Option<String>,
+    placement: Option<String>,
+    height: Option<String>,
+    area: Option<(usize, usize, usize, usize)>,
+    paths: Vec<PathQuery>,
+    output: Option<String>,
+    images: Option<String>,
+    scale: usize,
+}
+
+struct PathQuery {
+    map_type: MapType,
+    start: (f32, f32),
+    end: (f32, f32),
+}
+
+#[derive(Serialize)]
+struct Report {
+    width: usize,
+    height: usize,
+    playable_area: (usize, usize, usize, usize),
+    chokes: Vec<ChokeReport>,
+    overlord_spots: Vec<(f32, f32)>,
+    border_cells: usize,
+    choke_cells: usize,
+    /// Sizes of the connected ground areas, largest first
+    ground_areas: Vec<usize>,
+    air_cells: usize,
+    paths: Vec<PathReport>,
+}
+
+#[derive(Serialize)]
+struct ChokeReport {
+    main_line: ((f32, f32), (f32, f32)),
+    center: (f32, f32),
+    width: f32,
+    cells: usize,
+}
+
+#[derive(Serialize)]
+struct PathReport {
+    map_type: &'static str,
+    start: (f32, f32),
+    end: (f32, f32),
+    found: bool,
+    distance: f32,
+    path: Vec<(usize, usize)>,
+}
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("sc2pathlib: {}", message);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let options = parse_args(env::args().skip(1).collect())?;
+
+    let file = |explicit: &Option<String>, suffix: &str| -> Result<String, String> {
+        match (explicit, &options.prefix) {
+            (Some(path), _) => Ok(path.clone()),
+            (None, Some(prefix)) => Ok(format!("{}_{}.npy", prefix, suffix)),
+            (None, None) => Err(format!("no {} grid given\n\n{}", suffix, USAGE)),
+        }
+    };
+
+    let pathing = load_grid(&file(&options.pathing, "pathing")?)?;
+    let placement = load_grid(&file(&options.placement, "placement")?)?;
+    let height_map = load_grid(&file(&options.height, "height")?)?;
+
+    let area = match options.area {
+        Some(area) => area,
+        None => default_area(&pathing, &placement),
+    };
+    let map = Map::new(pathing, placement, height_map, area.0, area.1, area.2, area.3).map_err(|e| e.to_string())?;
+
+    let report = analyze(&map, area, &options.paths);
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+
+    match &options.output {
+        Some(output) => fs::write(output, json).map_err(|e| format!("{}: {}", output, e))?,
+        None => println!("{}", json),
+    }
+
+    if let Some(directory) = &options.images {
+        let paths: Vec<Vec<(usize, usize)>> = report.paths.iter().map(|path| path.path.clone()).collect();
+        save_images(&map, directory, options.scale, &paths)?;
+    }
+
+    Ok(())
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options { scale: 4,
+                                ..Options::default() };
+    let mut iter = args.into_iter();
+
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| iter.next().ok_or_else(|| format!("{} requires a value", name));
+
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            "--pathing" => options.pathing = Some(value(&arg)?),
+            "--placement" => options.placement = Some(value(&arg)?),
+            "--height" => options.height = Some(value(&arg)?),
+            "--area" => options.area = Some(parse_area(&value(&arg)?)?),
+            "--path" => options.paths.push(parse_path(&value(&arg)?)?),
+            "--output" => options.output = Some(value(&arg)?),
+            "--images" => options.images = Some(value(&arg)?),
+            "--scale" => {
+                options.scale = value(&arg)?.parse().map_err(|_| "--scale must be a positive number".to_string())?;
+                if options.scale == 0 {
+                    return Err("--scale must be a positive number".to_string());
+                }
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}\n\n{}", arg, USAGE)),
+            _ if options.prefix.is_none() => options.prefix = Some(arg),
+            _ => return Err(format!("unexpected argument {}\n\n{}", arg, USAGE)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_numbers<T: std::str::FromStr>(value: &str, count: usize) -> Result<Vec<T>, String> {
+    let numbers: Vec<T> = value.split(',')
+                               .map(|part| part.trim().parse::<T>())
+                               .collect::<Result<_, _>>()
+                               .map_err(|_| format!("invalid numbers '{}'", value))?;
+    if numbers.len() != count {
+        return Err(format!("expected {} numbers in '{}'", count, value));
+    }
+    Ok(numbers)
+}
+
+fn parse_area(value: &str) -> Result<(usize, usize, usize, usize), String> {
+    let numbers = parse_numbers::<usize>(value, 4)?;
+    Ok((numbers[0], numbers[1], numbers[2], numbers[3]))
+}
+
+fn parse_path(value: &str) -> Result<PathQuery, String> {
+    let parts: Vec<&str> = value.split(':').collect();
+    if parts.len() != 3 {
+        return Err(format!("path query '{}' must look like ground:x,y:x,y", value));
+    }
+
+    let map_type = match parts[0] {
+        "ground" => MapType::Ground,
+        "reaper" => MapType::Reaper,
+        "colossus" => MapType::Colossus,
+        "air" => MapType::Air,
+        other => return Err(format!("unknown map type '{}'", other)),
+    };
+    let start = parse_numbers::<f32>(parts[1], 2)?;
+    let end = parse_numbers::<f32>(parts[2], 2)?;
+
+    Ok(PathQuery { map_type,
+                   start: (start[0], start[1]),
+                   end: (end[0], end[1]) })
+}
+
+fn map_type_name(map_type: MapType) -> &'static str {
+    match map_type {
+        MapType::Ground => "ground",
+        MapType::Reaper => "reaper",
+        MapType::Colossus => "colossus",
+        MapType::Air => "air",
+    }
+}
+
+fn load_grid(file: &str) -> Result<Vec<Vec<usize>>, String> {
+    npy::load_grid(file).map_err(|e| format!("{}: {}", file, e))
+}
+
+/// Bounding box of walkable cells, kept one cell away from the edges as `Map::new` requires.
+fn default_area(pathing: &[Vec<usize>], placement: &[Vec<usize>]) -> (usize, usize, usize, usize) {
+    let width = pathing.len();
+    let height = pathing[0].len();
+    let mut area = (width, height, 0, 0);
+
+    for x in 0..width {
+        for y in 0..height {
+            if pathing[x][y] > 0 || placement[x][y] > 0 {
+                area = (area.0.min(x), area.1.min(y), area.2.max(x), area.3.max(y));
+            }
+        }
+    }
+
+    if area.0 > area.2 {
+        return (1, 1, width.saturating_sub(2), height.saturating_sub(2));
+    }
+
+    (area.0.max(1), area.1.max(1), area.2.min(width - 2), area.3.min(height - 2))
+}
+
+fn analyze(map: &Map, area: (usize, usize, usize, usize), queries: &[PathQuery]) -> Report {
+    let ground = &map.ground_pathing;
+
+    let chokes = map.chokes
+                    .iter()
+                    .map(|choke| {
+                        let (a, b) = choke.main_line;
+                        ChokeReport { main_line: choke.main_line,
+                                      center: ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0),
+                                      width: choke.min_length,
+                                      cells: choke.pixels.len() }
+                    })
+                    .collect();
+
+    let choke_cells = map.points.iter().flatten().filter(|point| point.is_choke).count();
+    let air_cells = map.air_pathing.map.iter().flatten().filter(|value| **value > 0).count();
+
+    let paths = queries.iter()
+                       .map(|query| {
+                           let (path, distance) = map.find_path(query.map_type, query.start, query.end, None);
+                           PathReport { map_type: map_type_name(query.map_type),
+                                        start: query.start,
+                                        end: query.end,
+                                        found: !path.is_empty(),
+                                        distance,
+                                        path }
+                       })
+                       .collect();
+
+    Report { width: ground.width,
+             height: ground.height,
+             playable_area: area,
+             chokes,
+             overlord_spots: map.overlord_spots.clone(),
+             border_cells: map.get_borders().len(),
+             choke_cells,
+             ground_areas: connected_areas(ground),
+             air_cells,
+             paths }
+}
+
+/// Sizes of the 8-connected pathable areas, largest first.
+fn connected_areas(path_find: &PathFind) -> Vec<usize> {
+    let width = path_find.width;
+    let height = path_find.height;
+    let mut visited = vec![vec![false; height]; width];
+    let mut sizes = Vec::new();
+
+    for x in 0..width {
+        for y in 0..height {
+            if visited[x][y] || path_find.map[x][y] == 0 {
+                continue;
+            }
+
+            let mut size = 0;
+            let mut stack = vec![(x, y)];
+            visited[x][y] = true;
+
+            while let Some((cx, cy)) = stack.pop() {
+                size += 1;
+                let neighbours = (cx.saturating_sub(1)..(cx + 2).min(width))
+                    .flat_map(|nx| (cy.saturating_sub(1)..(cy + 2).min(height)).map(move |ny| (nx, ny)));
+                for (nx, ny) in neighbours {
+                    if !visited[nx][ny] && path_find.map[nx][ny] > 0 {
+                        visited[nx][ny] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+
+            sizes.push(size);
+        }
+    }
+
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes
+}
+
+fn save_images(map: &Map, directory: &str, scale: usize, paths: &[Vec<(usize, usize)>]) -> Result<(), String> {
+    fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory, e))?;
+
+    let images = vec![("ground.png", map.render_layer(MapType::Ground, false, paths)),
+                      ("climbs.png", map.render_climbs()),
+                      ("chokes.png", map.render_chokes()),
+                      ("overlord_spots.png", map.render_overlord_spots()),];
+
+    for (name, image) in images {
+        let file = Path::new(directory).join(name);
+        let file = file.to_string_lossy();
+        image.scaled(scale).save(&file).map_err(|e| format!("{}: {}", file, e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use std::io;
+
+/// Little-endian writer used by the binary cache format.
+pub struct BinaryWriter {
+    pub buffer: Vec<u8>,
+}
+
+impl BinaryWriter {
+    pub fn new() -> Self { BinaryWriter { buffer: Vec::new() } }
+
+    pub fn write_bytes(&mut self, value: &[u8]) { self.buffer.extend_from_slice(value); }
+
+    pub fn write_u8(&mut self, value: u8) { self.buffer.push(value); }
+
+    pub fn write_bool(&mut self, value: bool) { self.buffer.push(value as u8); }
+
+    pub fn write_u32(&mut self, value: u32) { self.buffer.extend_from_slice(&value.to_le_bytes()); }
+
+    pub fn write_i32(&mut self, value: i32) { self.buffer.extend_from_slice(&value.to_le_bytes()); }
+
+    pub fn write_u64(&mut self, value: u64) { self.buffer.extend_from_slice(&value.to_le_bytes()); }
+
+    pub fn write_f32(&mut self, value: f32) { self.buffer.extend_from_slice(&value.to_le_bytes()); }
+
+    /// LEB128 encoding, pathing grids are mostly zeros and ones so this keeps them at a byte per cell.
+    pub fn write_usize(&mut self, value: usize) {
+        let mut value = value as u64;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.buffer.push(byte);
+                return;
+            }
+            self.buffer.push(byte | 0x80);
+        }
+    }
+
+    pub fn write_point(&mut self, value: (usize, usize)) {
+        self.write_usize(value.0);
+        self.write_usize(value.1);
+    }
+
+    pub fn write_point_f32(&mut self, value: (f32, f32)) {
+        self.write_f32(value.0);
+        self.write_f32(value.1);
+    }
+
+    pub fn write_points(&mut self, values: &[(usize, usize)]) {
+        self.write_usize(values.len());
+        for value in values {
+            self.write_point(*value);
+        }
+    }
+
+    pub fn write_grid(&mut self, grid: &[Vec<usize>]) {
+        self.write_usize(grid.len());
+        self.write_usize(if grid.is_empty() { 0 } else { grid[0].len() });
+        for column in grid {
+            for value in column {
+                self.write_usize(*value);
+            }
+        }
+    }
+}
+
+/// Reader counterpart of `BinaryWriter`, every read is bounds checked.
+pub struct BinaryReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self { BinaryReader { data, position: 0 } }
+
+    pub fn is_empty(&self) -> bool { self.position >= self.data.len() }
+
+    pub fn remaining(&self) -> usize { self.data.len() - self.position }
+
+    pub fn read_bytes(&mut self, count: usize) -> io::Result<&'a [u8]> {
+        if self.remaining() < count {
+            return Err(invalid_data("unexpected end of data"));
+        }
+
+        let slice = &self.data[self.position..self.position + count];
+        self.position += count;
+        Ok(slice)
+    }
+
+    pub fn read_u8(&mut self) -> io::Result<u8> { Ok(self.read_bytes(1)?[0]) }
+
+    pub fn read_bool(&mut self) -> io::Result<bool> { Ok(self.read_u8()? != 0) }
+
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub fn read_i32(&mut self) -> io::Result<i32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(i32::from_le_bytes(bytes))
+    }
+
+    pub fn read_u64(&mut self) -> io::Result<u64> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub fn read_f32(&mut self) -> io::Result<f32> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(f32::from_le_bytes(bytes))
+    }
+
+    pub fn read_usize(&mut self) -> io::Result<usize> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            if shift >= 64 {
+                return Err(invalid_data("integer overflow"));
+            }
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result as usize);
+            }
+            shift += 7;
+        }
+    }
+
+    pub fn read_point(&mut self) -> io::Result<(usize, usize)> { Ok((self.read_usize()?, self.read_usize()?)) }
+
+    pub fn read_point_f32(&mut self) -> io::Result<(f32, f32)> { Ok((self.read_f32()?, self.read_f32()?)) }
+
+    /// Reads a length prefix and checks that at least `min_item_size` bytes per item remain.
+    pub fn read_len(&mut self, min_item_size: usize) -> io::Result<usize> {
+        let len = self.read_usize()?;
+        if len.saturating_mul(min_item_size) > self.remaining() {
+            return Err(invalid_data("length exceeds data"));
+        }
+        Ok(len)
+    }
+
+    pub fn read_points(&mut self) -> io::Result<Vec<(usize, usize)>> {
+        let len = self.read_len(2)?;
+        let mut result = Vec::with_capacity(len);
+        for _ in 0..len {
+            result.push(self.read_point()?);
+        }
+        Ok(result)
+    }
+
+    pub fn read_grid(&mut self) -> io::Result<Vec<Vec<usize>>> {
+        let width = self.read_usize()?;
+        let height = self.read_usize()?;
+        if width == 0 || height == 0 {
+            return Err(invalid_data("empty grid"));
+        }
+        if width.saturating_mul(height) > self.remaining() {
+            return Err(invalid_data("grid exceeds data"));
+        }
+
+        let mut grid = vec![vec![0; height]; width];
+        for column in grid.iter_mut() {
+            for value in column.iter_mut() {
+                *value = self.read_usize()?;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+pub fn invalid_data(message: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message.to_string()) }
+
+/// 64 bit FNV-1a, stable between builds unlike `std::collections::hash_map::DefaultHasher`.
+pub struct Fnv1a {
+    hash: u64,
+}
+
+impl Fnv1a {
+    pub fn new() -> Self { Fnv1a { hash: 0xcbf2_9ce4_8422_2325 } }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    pub fn write_usize(&mut self, value: usize) { self.write(&(value as u64).to_le_bytes()); }
+
+    pub fn write_grid(&mut self, grid: &[Vec<usize>]) {
+        self.write_usize(grid.len());
+        self.write_usize(if grid.is_empty() { 0 } else { grid[0].len() });
+        for column in grid {
+            for value in column {
+                self.write_usize(*value);
+            }
+        }
+    }
+
+    pub fn finish(&self) -> u64 { self.hash }
+}
@@ -0,0 +1,488 @@
+//! C ABI over `PathFind` and `Map` for bots that can't load the python module.
+//!
+//! Handles are opaque pointers created by the `*_new` functions and released with the matching `*_free`.
+//! Grids are passed row by row, the cell `(x, y)` is at `grid[y * width + x]` as in the game's image data.
+//! Functions returning paths or points write at most `capacity` items into the caller's buffer and return
+//! the total count, so a caller can retry with a larger buffer.
+//!
+//! # Safety
+//!
+//! Every pointer argument must be null or valid for the documented number of items, handles must come from
+//! this library and must not be used after they are freed.
+#![allow(clippy::missing_safety_doc)]
+
+use crate::mapping::map::Map;
+use crate::mapping::{MapType, MapsType};
+use crate::path_find::PathFind;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// Grid cell.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Sc2Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// Position in world coordinates.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Sc2PointF {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Summary of a choke, the cells are available through `sc2pathlib_map_choke_pixels`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Sc2Choke {
+    pub main_line_start: Sc2PointF,
+    pub main_line_end: Sc2PointF,
+    pub min_length: f32,
+    pub pixel_count: usize,
+}
+
+// Panics must not unwind into C, they are turned into the default value instead
+fn guard<T>(default: T, f: impl FnOnce() -> T) -> T { panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(default) }
+
+unsafe fn read_grid<T: Copy + Into<usize>>(grid: *const T, width: usize, height: usize) -> Option<Vec<Vec<usize>>> {
+    if grid.is_null() || width == 0 || height == 0 {
+        return None;
+    }
+
+    let cells = slice::from_raw_parts(grid, width.checked_mul(height)?);
+    let mut result = vec![vec![0; height]; width];
+    for (x, column) in result.iter_mut().enumerate() {
+        for (y, value) in column.iter_mut().enumerate() {
+            *value = cells[y * width + x].into();
+        }
+    }
+
+    Some(result)
+}
+
+unsafe fn read_points<'a, T>(points: *const T, count: usize) -> &'a [T] {
+    if points.is_null() || count == 0 {
+        return &[];
+    }
+
+    slice::from_raw_parts(points, count)
+}
+
+unsafe fn write_items<T: Copy>(items: &[T], out: *mut T, capacity: usize) -> usize {
+    if !out.is_null() {
+        for (index, item) in items.iter().take(capacity).enumerate() {
+            *out.add(index) = *item;
+        }
+    }
+
+    items.len()
+}
+
+unsafe fn write_path(result: (Vec<(usize, usize)>, f32),
+                     out_path: *mut Sc2Point,
+                     capacity: usize,
+                     out_distance: *mut f32)
+                     -> usize {
+    let (path, distance) = result;
+    if !out_distance.is_null() {
+        *out_distance = distance;
+    }
+
+    let points: Vec<Sc2Point> = path.into_iter().map(|(x, y)| Sc2Point { x, y }).collect();
+    write_items(&points, out_path, capacity)
+}
+
+fn heuristic(value: i32) -> Option<u8> {
+    if value < 0 {
+        None
+    } else {
+        Some(value as u8)
+    }
+}
+
+fn point_f32(point: Sc2PointF) -> (f32, f32) { (point.x, point.y) }
+
+fn point_usize(point: Sc2Point) -> (usize, usize) { (point.x, point.y) }
+
+// PathFind
+
+/// Creates a pathfinder from a `width * height` grid where 0 is blocked, returns null on invalid input.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_new(grid: *const usize, width: usize, height: usize) -> *mut PathFind {
+    let grid = match read_grid(grid, width, height) {
+        Some(grid) => grid,
+        None => return ptr::null_mut(),
+    };
+
+    match PathFind::new(grid) {
+        Ok(path_find) => Box::into_raw(Box::new(path_find)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_free(path_find: *mut PathFind) {
+    if !path_find.is_null() {
+        drop(Box::from_raw(path_find));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_width(path_find: *const PathFind) -> usize {
+    path_find.as_ref().map_or(0, |path_find| path_find.width)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_height(path_find: *const PathFind) -> usize {
+    path_find.as_ref().map_or(0, |path_find| path_find.height)
+}
+
+/// Value of the cell, 0 when blocked or out of bounds.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_get(path_find: *const PathFind, point: Sc2Point) -> usize {
+    match path_find.as_ref() {
+        Some(path_find) if point.x < path_find.width && point.y < path_find.height => {
+            path_find.map[point.x][point.y]
+        }
+        _ => 0,
+    }
+}
+
+/// Restores the grid the pathfinder was created with.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_reset(path_find: *mut PathFind) {
+    if let Some(path_find) = path_find.as_mut() {
+        path_find.reset();
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_create_block(path_find: *mut PathFind,
+                                                          center: Sc2PointF,
+                                                          width: usize,
+                                                          height: usize) {
+    if let Some(path_find) = path_find.as_mut() {
+        guard((), || path_find.create_block(point_f32(center), (width, height)));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_remove_block(path_find: *mut PathFind,
+                                                          center: Sc2PointF,
+                                                          width: usize,
+                                                          height: usize) {
+    if let Some(path_find) = path_find.as_mut() {
+        guard((), || path_find.remove_block(point_f32(center), (width, height)));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_normalize_influence(path_find: *mut PathFind, value: usize) {
+    if let Some(path_find) = path_find.as_mut() {
+        path_find.normalize_influence(value);
+    }
+}
+
+/// Adds influence fading with euclidean distance around each position.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_add_influence(path_find: *mut PathFind,
+                                                           positions: *const Sc2Point,
+                                                           count: usize,
+                                                           max: f32,
+                                                           distance: f32) {
+    if let Some(path_find) = path_find.as_mut() {
+        let positions: Vec<(usize, usize)> = read_points(positions, count).iter().map(|p| point_usize(*p)).collect();
+        guard((), || path_find.add_influence(&positions, max, distance));
+    }
+}
+
+/// Adds the same influence to every cell within euclidean distance of each position.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_add_influence_flat(path_find: *mut PathFind,
+                                                                positions: *const Sc2Point,
+                                                                count: usize,
+                                                                max: f32,
+                                                                distance: f32) {
+    if let Some(path_find) = path_find.as_mut() {
+        let positions: Vec<(usize, usize)> = read_points(positions, count).iter().map(|p| point_usize(*p)).collect();
+        guard((), || path_find.add_influence_flat(&positions, max, distance));
+    }
+}
+
+/// Adds influence fading with walking distance around each position.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_add_walk_influence(path_find: *mut PathFind,
+                                                                positions: *const Sc2Point,
+                                                                count: usize,
+                                                                max: f32,
+                                                                distance: f32) {
+    if let Some(path_find) = path_find.as_mut() {
+        let positions: Vec<(usize, usize)> = read_points(positions, count).iter().map(|p| point_usize(*p)).collect();
+        guard((), || path_find.add_walk_influence(&positions, max, distance));
+    }
+}
+
+/// Shortest path ignoring influence, `heuristic` below 0 selects the default heuristic.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_find_path(path_find: *const PathFind,
+                                                       start: Sc2Point,
+                                                       end: Sc2Point,
+                                                       heuristic_type: i32,
+                                                       out_path: *mut Sc2Point,
+                                                       capacity: usize,
+                                                       out_distance: *mut f32)
+                                                       -> usize {
+    match path_find.as_ref() {
+        Some(path_find) => {
+            let result = guard((Vec::new(), 0.0), || {
+                path_find.find_path(point_usize(start), point_usize(end), heuristic(heuristic_type))
+            });
+            write_path(result, out_path, capacity, out_distance)
+        }
+        None => 0,
+    }
+}
+
+/// Shortest path ignoring influence for units larger than a single cell.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_find_path_large(path_find: *const PathFind,
+                                                             start: Sc2Point,
+                                                             end: Sc2Point,
+                                                             heuristic_type: i32,
+                                                             out_path: *mut Sc2Point,
+                                                             capacity: usize,
+                                                             out_distance: *mut f32)
+                                                             -> usize {
+    match path_find.as_ref() {
+        Some(path_find) => {
+            let result = guard((Vec::new(), 0.0), || {
+                path_find.find_path_large(point_usize(start), point_usize(end), heuristic(heuristic_type))
+            });
+            write_path(result, out_path, capacity, out_distance)
+        }
+        None => 0,
+    }
+}
+
+/// Path using influence values as cost.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_pathfind_find_path_influence(path_find: *const PathFind,
+                                                                 start: Sc2Point,
+                                                                 end: Sc2Point,
+                                                                 heuristic_type: i32,
+                                                                 out_path: *mut Sc2Point,
+                                                                 capacity: usize,
+                                                                 out_distance: *mut f32)
+                                                                 -> usize {
+    match path_find.as_ref() {
+        Some(path_find) => {
+            let result = guard((Vec::new(), 0.0), || {
+                path_find.find_path_influence(point_usize(start), point_usize(end), heuristic(heuristic_type))
+            });
+            write_path(result, out_path, capacity, out_distance)
+        }
+        None => 0,
+    }
+}
+
+// Map
+
+/// Analyses the map from `width * height` grids as sent by the game, returns null on invalid input.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_new(pathing: *const u8,
+                                            placement: *const u8,
+                                            height_map: *const u8,
+                                            width: usize,
+                                            height: usize,
+                                            x_start: usize,
+                                            y_start: usize,
+                                            x_end: usize,
+                                            y_end: usize)
+                                            -> *mut Map {
+    let grids = (read_grid(pathing, width, height),
+                 read_grid(placement, width, height),
+                 read_grid(height_map, width, height));
+    let (pathing, placement, height_map) = match grids {
+        (Some(pathing), Some(placement), Some(height_map)) => (pathing, placement, height_map),
+        _ => return ptr::null_mut(),
+    };
+
+    let map = guard(None, || Map::new(pathing, placement, height_map, x_start, y_start, x_end, y_end).ok());
+    match map {
+        Some(map) => Box::into_raw(Box::new(map)),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_free(map: *mut Map) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+/// Pathing layer of the map, `map_type` uses the values of `MapType`. The pointer is owned by the map.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_pathing(map: *mut Map, map_type: u8) -> *mut PathFind {
+    match (map.as_mut(), MapType::from_u8(map_type)) {
+        (Some(map), Some(map_type)) => map.get_map_mut(map_type),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Reset all mapping to their originals.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_reset(map: *mut Map) {
+    if let Some(map) = map.as_mut() {
+        map.reset();
+    }
+}
+
+/// Blocks the area on all ground layers.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_create_block(map: *mut Map, center: Sc2PointF, width: usize, height: usize) {
+    if let Some(map) = map.as_mut() {
+        guard((), || map.create_block(point_f32(center), (width, height)));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_remove_block(map: *mut Map, center: Sc2PointF, width: usize, height: usize) {
+    if let Some(map) = map.as_mut() {
+        guard((), || map.remove_block(point_f32(center), (width, height)));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_normalize_influence(map: *mut Map, value: usize) {
+    if let Some(map) = map.as_mut() {
+        map.normalize_influence(value);
+    }
+}
+
+/// Adds influence fading with walking distance to the ground layers.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_add_influence_walk(map: *mut Map,
+                                                           positions: *const Sc2PointF,
+                                                           count: usize,
+                                                           influence: f32,
+                                                           distance: f32) {
+    if let Some(map) = map.as_mut() {
+        let positions: Vec<(f32, f32)> = read_points(positions, count).iter().map(|p| point_f32(*p)).collect();
+        guard((), || map.add_influence_walk(&positions, influence, distance));
+    }
+}
+
+/// Adds full influence up to `min` fading out to `max`, `maps_type` uses the values of `MapsType`.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_add_influence_fading(map: *mut Map,
+                                                             maps_type: usize,
+                                                             positions: *const Sc2PointF,
+                                                             count: usize,
+                                                             influence: f32,
+                                                             min: f32,
+                                                             max: f32) {
+    if let (Some(map), Some(maps_type)) = (map.as_mut(), MapsType::from_usize(maps_type)) {
+        let positions: Vec<(f32, f32)> = read_points(positions, count).iter().map(|p| point_f32(*p)).collect();
+        guard((), || map.add_influence_fading(maps_type, &positions, influence, min, max));
+    }
+}
+
+/// Shortest path on the selected layer ignoring influence.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_find_path(map: *const Map,
+                                                  map_type: u8,
+                                                  start: Sc2PointF,
+                                                  end: Sc2PointF,
+                                                  heuristic_type: i32,
+                                                  out_path: *mut Sc2Point,
+                                                  capacity: usize,
+                                                  out_distance: *mut f32)
+                                                  -> usize {
+    match (map.as_ref(), MapType::from_u8(map_type)) {
+        (Some(map), Some(map_type)) => {
+            let result = guard((Vec::new(), 0.0), || {
+                map.find_path(map_type, point_f32(start), point_f32(end), heuristic(heuristic_type))
+            });
+            write_path(result, out_path, capacity, out_distance)
+        }
+        _ => 0,
+    }
+}
+
+/// Path on the selected layer using influence values as cost.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_find_path_influence(map: *const Map,
+                                                            map_type: u8,
+                                                            start: Sc2PointF,
+                                                            end: Sc2PointF,
+                                                            heuristic_type: i32,
+                                                            out_path: *mut Sc2Point,
+                                                            capacity: usize,
+                                                            out_distance: *mut f32)
+                                                            -> usize {
+    match (map.as_ref(), MapType::from_u8(map_type)) {
+        (Some(map), Some(map_type)) => {
+            let result = guard((Vec::new(), 0.0), || {
+                map.find_path_influence(map_type, point_f32(start), point_f32(end), heuristic(heuristic_type))
+            });
+            write_path(result, out_path, capacity, out_distance)
+        }
+        _ => 0,
+    }
+}
+
+/// Writes the overlord spots into `out` and returns their count.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_overlord_spots(map: *const Map, out: *mut Sc2PointF, capacity: usize) -> usize {
+    match map.as_ref() {
+        Some(map) => {
+            let spots: Vec<Sc2PointF> = map.overlord_spots.iter().map(|(x, y)| Sc2PointF { x: *x, y: *y }).collect();
+            write_items(&spots, out, capacity)
+        }
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_choke_count(map: *const Map) -> usize {
+    map.as_ref().map_or(0, |map| map.chokes.len())
+}
+
+/// Fills `out` with the choke at `index`, returns false when the index is out of range.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_choke(map: *const Map, index: usize, out: *mut Sc2Choke) -> bool {
+    let choke = match map.as_ref().and_then(|map| map.chokes.get(index)) {
+        Some(choke) => choke,
+        None => return false,
+    };
+
+    if !out.is_null() {
+        let (start, end) = choke.main_line;
+        *out = Sc2Choke { main_line_start: Sc2PointF { x: start.0, y: start.1 },
+                          main_line_end: Sc2PointF { x: end.0, y: end.1 },
+                          min_length: choke.min_length,
+                          pixel_count: choke.pixels.len() };
+    }
+
+    true
+}
+
+/// Writes the cells of the choke at `index` into `out` and returns their count.
+#[no_mangle]
+pub unsafe extern "C" fn sc2pathlib_map_choke_pixels(map: *const Map,
+                                                     index: usize,
+                                                     out: *mut Sc2Point,
+                                                     capacity: usize)
+                                                     -> usize {
+    match map.as_ref().and_then(|map| map.chokes.get(index)) {
+        Some(choke) => {
+            let pixels: Vec<Sc2Point> = choke.pixels.iter().map(|(x, y)| Sc2Point { x: *x, y: *y }).collect();
+            write_items(&pixels, out, capacity)
+        }
+        None => 0,
+    }
+}
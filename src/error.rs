@@ -0,0 +1,47 @@
+use std::fmt;
+
+/// Errors returned by the Rust API when the input grids are not usable.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// A grid has no columns or no rows.
+    EmptyGrid,
+    /// A grid is not rectangular or does not match the size of the other grids, sizes are `(width, height)`.
+    GridSizeMismatch { expected: (usize, usize), found: (usize, usize) },
+    /// The playable area does not fit inside the grid with a border of at least one cell.
+    InvalidPlayableArea { start: (usize, usize), end: (usize, usize) },
+    /// Game image data with other than 1 or 8 bits per pixel.
+    UnsupportedBitsPerPixel(u32),
+    /// Game image data with a byte count that doesn't match its size.
+    ImageDataSize { expected: usize, found: usize },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyGrid => write!(f, "grid must not be empty"),
+            Error::GridSizeMismatch { expected, found } => {
+                write!(f,
+                       "grid size {}x{} does not match expected size {}x{}",
+                       found.0, found.1, expected.0, expected.1)
+            }
+            Error::InvalidPlayableArea { start, end } => {
+                write!(f,
+                       "playable area ({}, {}) - ({}, {}) does not fit inside the grid",
+                       start.0, start.1, end.0, end.1)
+            }
+            Error::UnsupportedBitsPerPixel(bits) => write!(f, "unsupported image data with {} bits per pixel", bits),
+            Error::ImageDataSize { expected, found } => {
+                write!(f, "image data has {} bytes, expected {}", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(feature = "python")]
+impl From<Error> for pyo3::PyErr {
+    fn from(error: Error) -> pyo3::PyErr { pyo3::exceptions::ValueError::py_err(error.to_string()) }
+}
@@ -0,0 +1,149 @@
+use crate::error::{Error, Result};
+use crate::mapping::map::Map;
+use crate::path_find::PathFind;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyBytes;
+
+/// Grid as sent by the game API in `ResponseGameInfo.start_raw`.
+///
+/// Pathing and placement use 1 bit per pixel packed with the most significant bit first,
+/// the terrain height uses 8 bits. The first row is `y = 0`, the same orientation python-sc2 uses.
+#[derive(Copy, Clone, Debug)]
+pub struct ImageData<'a> {
+    pub data: &'a [u8],
+    pub bits_per_pixel: u32,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a> ImageData<'a> {
+    pub fn new(data: &'a [u8], bits_per_pixel: u32, width: usize, height: usize) -> Self {
+        ImageData { data,
+                    bits_per_pixel,
+                    width,
+                    height }
+    }
+
+    /// Unpacks the image into the `grid[x][y]` layout used by `Map` and `PathFind`.
+    pub fn decode(&self) -> Result<Vec<Vec<usize>>> {
+        if self.width == 0 || self.height == 0 {
+            return Err(Error::EmptyGrid);
+        }
+
+        let pixels = self.width * self.height;
+        let expected = match self.bits_per_pixel {
+            1 => (pixels - 1) / 8 + 1,
+            8 => pixels,
+            bits => return Err(Error::UnsupportedBitsPerPixel(bits)),
+        };
+        if self.data.len() != expected {
+            return Err(Error::ImageDataSize { expected,
+                                              found: self.data.len() });
+        }
+
+        let mut grid = vec![vec![0; self.height]; self.width];
+        for (x, column) in grid.iter_mut().enumerate() {
+            for (y, value) in column.iter_mut().enumerate() {
+                let index = y * self.width + x;
+                *value = if self.bits_per_pixel == 1 {
+                    ((self.data[index / 8] >> (7 - index % 8)) & 1) as usize
+                } else {
+                    self.data[index] as usize
+                };
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Unpacks the image with every non-zero pixel as 1, pathing and placement grids are 8 bit on some game versions.
+    pub fn decode_mask(&self) -> Result<Vec<Vec<usize>>> {
+        let mut grid = self.decode()?;
+        for value in grid.iter_mut().flatten() {
+            *value = (*value > 0) as usize;
+        }
+
+        Ok(grid)
+    }
+}
+
+impl PathFind {
+    /// Pathfinder from a pathing or placement grid as sent by the game.
+    pub fn from_image_data(image: &ImageData) -> Result<PathFind> { PathFind::new(image.decode_mask()?) }
+}
+
+impl Map {
+    /// Analyses the map from the grids as sent by the game.
+    pub fn from_image_data(pathing: &ImageData,
+                           placement: &ImageData,
+                           terrain_height: &ImageData,
+                           x_start: usize,
+                           y_start: usize,
+                           x_end: usize,
+                           y_end: usize)
+                           -> Result<Map> {
+        Map::new(pathing.decode_mask()?,
+                 placement.decode_mask()?,
+                 terrain_height.decode()?,
+                 x_start,
+                 y_start,
+                 x_end,
+                 y_end)
+    }
+
+    /// Replaces the creep layer from the creep grid the game sends in every observation.
+    pub fn set_creep_image_data(&mut self, creep: &ImageData) -> Result<()> { self.set_creep(creep.decode_mask()?) }
+}
+
+/// Reads an `ImageData` protobuf message, anything with `data`, `bits_per_pixel` and `size.x`/`size.y` works.
+#[cfg(feature = "python")]
+fn extract_image_data(obj: &PyAny) -> PyResult<ImageData<'_>> {
+    let data = obj.getattr("data")?.downcast::<PyBytes>()?.as_bytes();
+    let bits_per_pixel: u32 = obj.getattr("bits_per_pixel")?.extract()?;
+    let size = obj.getattr("size")?;
+    Ok(ImageData::new(data, bits_per_pixel, size.getattr("x")?.extract()?, size.getattr("y")?.extract()?))
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PathFind {
+    /// Creates the pathfinder from an `ImageData` message such as `game_info._proto.start_raw.pathing_grid`.
+    #[staticmethod]
+    #[name = "from_image_data"]
+    fn from_image_data_py(image: &PyAny) -> PyResult<PathFind> {
+        Ok(PathFind::from_image_data(&extract_image_data(image)?)?)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Creates the map from the `ImageData` messages in `game_info._proto.start_raw`.
+    #[staticmethod]
+    #[name = "from_image_data"]
+    #[allow(clippy::too_many_arguments)]
+    fn from_image_data_py(py: Python,
+                          pathing: &PyAny,
+                          placement: &PyAny,
+                          terrain_height: &PyAny,
+                          x_start: usize,
+                          y_start: usize,
+                          x_end: usize,
+                          y_end: usize)
+                          -> PyResult<Map> {
+        let pathing = extract_image_data(pathing)?;
+        let placement = extract_image_data(placement)?;
+        let terrain_height = extract_image_data(terrain_height)?;
+        Ok(py.allow_threads(|| {
+                 Map::from_image_data(&pathing, &placement, &terrain_height, x_start, y_start, x_end, y_end)
+             })?)
+    }
+
+    /// Replaces the creep layer from an `ImageData` message such as `observation.raw_data.map_state.creep`.
+    #[name = "set_creep_image_data"]
+    fn set_creep_image_data_py(&mut self, creep: &PyAny) -> PyResult<()> {
+        Ok(self.set_creep_image_data(&extract_image_data(creep)?)?)
+    }
+}
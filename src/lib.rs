@@ -1,13 +1,32 @@
 #![allow(dead_code)]
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+mod binary;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod error;
+pub mod image_data;
 pub mod mapping;
+pub mod npy;
+#[cfg(feature = "python")]
+pub mod numpy_grid;
 pub mod path_find;
+#[cfg(feature = "python")]
+mod pickle;
+pub mod render;
+
+pub use error::{Error, Result};
 
 /// This module is a python module implemented in Rust.
+#[cfg(feature = "python")]
 #[pymodule]
-fn sc2pathlib(_py: Python, m: &PyModule) -> PyResult<()> {
+fn sc2pathlib(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<path_find::PathFind>()?;
     m.add_class::<mapping::map::Map>()?;
+    m.add_class::<path_find::footprint::Footprint>()?;
+    m.add_class::<path_find::obstacles::Obstacle>()?;
+    m.add_class::<mapping::reaper::ReaperJump>()?;
+    pickle::add_functions(py, m)?;
     Ok(())
 }
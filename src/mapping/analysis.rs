@@ -0,0 +1,71 @@
+use crate::mapping::chokes::Choke;
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+use crate::mapping::ramps::Ramp;
+use crate::mapping::regions::Region;
+#[cfg(feature = "python")]
+use pyo3::exceptions::ValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Result of the map analysis in a form that can be shared with other tools.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapAnalysis {
+    pub width: usize,
+    pub height: usize,
+    /// `input_hash` of the map as hex, JSON numbers lose precision above 2^53 in most parsers
+    pub input_hash: String,
+    pub overlord_spots: Vec<(f32, f32)>,
+    pub border_cells: usize,
+    pub choke_cells: usize,
+    pub chokes: Vec<Choke>,
+    #[serde(default)]
+    pub regions: Vec<Region>,
+    #[serde(default)]
+    pub ramps: Vec<Ramp>,
+    /// Every cell as `points[x][y]`, only included when requested as it is large
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<Vec<Vec<MapPoint>>>,
+}
+
+impl MapAnalysis {
+    pub fn to_json(&self) -> serde_json::Result<String> { serde_json::to_string(self) }
+
+    pub fn from_json(json: &str) -> serde_json::Result<MapAnalysis> { serde_json::from_str(json) }
+}
+
+impl Map {
+    /// Summary of the analysis done in `Map::new`, optionally with every map point.
+    pub fn analysis(&self, include_points: bool) -> MapAnalysis {
+        let points = self.points.iter().flatten();
+
+        MapAnalysis { width: self.ground_pathing.width,
+                      height: self.ground_pathing.height,
+                      input_hash: format!("{:016x}", self.input_hash),
+                      overlord_spots: self.overlord_spots.clone(),
+                      border_cells: points.clone().filter(|point| point.is_border).count(),
+                      choke_cells: points.filter(|point| point.is_choke).count(),
+                      chokes: self.chokes.clone(),
+                      regions: self.regions.clone(),
+                      ramps: self.ramps.clone(),
+                      points: if include_points { Some(self.points.clone()) } else { None } }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Analysis summary with chokes and overlord spots as JSON.
+    #[args(include_points = "false")]
+    fn analysis_json(&self, include_points: bool) -> PyResult<String> {
+        self.analysis(include_points).to_json().map_err(|e| ValueError::py_err(e.to_string()))
+    }
+
+    /// Chokes from JSON created by `analysis_json`.
+    #[staticmethod]
+    fn chokes_from_json(json: &str) -> PyResult<Vec<Choke>> {
+        let analysis = MapAnalysis::from_json(json).map_err(|e| ValueError::py_err(e.to_string()))?;
+        Ok(analysis.chokes)
+    }
+}
@@ -0,0 +1,316 @@
+use crate::binary::{invalid_data, BinaryReader, BinaryWriter, Fnv1a};
+use crate::mapping::chokes::Choke;
+use crate::mapping::map::Map;
+use crate::mapping::map_point::{Cliff, MapPoint};
+use crate::mapping::ramps::solve_ramps;
+use crate::mapping::reaper::solve_reaper_jumps;
+use crate::mapping::region_graph::RegionGraph;
+use crate::mapping::regions::collect_regions;
+#[cfg(feature = "python")]
+use crate::numpy_grid::{extract_grid, Orientation};
+use crate::path_find::PathFind;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::fs;
+use std::io;
+
+const MAGIC: &[u8; 8] = b"SC2PMAP\0";
+/// Increase whenever the layout below or the analysis in `Map::new` changes.
+pub const CACHE_VERSION: u32 = 9;
+
+const FLAG_PATHABLE: u8 = 0b0000_0001;
+const FLAG_WALKABLE: u8 = 0b0000_0010;
+const FLAG_CLIMBABLE: u8 = 0b0000_0100;
+const FLAG_OVERLORD_SPOT: u8 = 0b0000_1000;
+const FLAG_BORDER: u8 = 0b0001_0000;
+const FLAG_CHOKE: u8 = 0b0010_0000;
+const FLAG_PLACEABLE: u8 = 0b0100_0000;
+
+/// Content hash of the grids and playable area `Map::new` was built from.
+pub fn input_hash(pathing: &[Vec<usize>],
+                  placement: &[Vec<usize>],
+                  height_map: &[Vec<usize>],
+                  x_start: usize,
+                  y_start: usize,
+                  x_end: usize,
+                  y_end: usize)
+                  -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher.write_grid(pathing);
+    hasher.write_grid(placement);
+    hasher.write_grid(height_map);
+    hasher.write_usize(x_start);
+    hasher.write_usize(y_start);
+    hasher.write_usize(x_end);
+    hasher.write_usize(y_end);
+    hasher.finish()
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Content hash of the grids the map was created from, used to detect a stale cache.
+    #[getter(input_hash)]
+    fn get_input_hash(&self) -> u64 { self.input_hash }
+
+    #[staticmethod]
+    #[name = "input_hash_of"]
+    #[args(orientation = "\"xy\"")]
+    #[allow(clippy::too_many_arguments)]
+    fn input_hash_of_py(pathing: &PyAny,
+                        placement: &PyAny,
+                        height_map: &PyAny,
+                        x_start: usize,
+                        y_start: usize,
+                        x_end: usize,
+                        y_end: usize,
+                        orientation: &str)
+                        -> PyResult<u64> {
+        let orientation = Orientation::parse(orientation)?;
+        let pathing = extract_grid(pathing, orientation)?;
+        let placement = extract_grid(placement, orientation)?;
+        let height_map = extract_grid(height_map, orientation)?;
+        Ok(input_hash(&pathing, &placement, &height_map, x_start, y_start, x_end, y_end))
+    }
+
+    /// Saves the map and its analysis to a binary file.
+    #[name = "save"]
+    fn save_py(&self, py: Python, path: &str) -> PyResult<()> {
+        py.allow_threads(|| self.save(path))?;
+        Ok(())
+    }
+
+    /// Loads a map saved with `save`, failing if it was created from different grids.
+    #[staticmethod]
+    #[name = "load"]
+    fn load_py(py: Python, path: &str, expected_hash: u64) -> PyResult<Map> {
+        Ok(py.allow_threads(|| Map::load(path, expected_hash))?)
+    }
+}
+
+impl Map {
+    pub fn save(&self, path: &str) -> io::Result<()> { fs::write(path, self.to_bytes()) }
+
+    pub fn load(path: &str, expected_hash: u64) -> io::Result<Map> {
+        let data = fs::read(path)?;
+        let map = Map::from_bytes(&data)?;
+
+        if map.input_hash != expected_hash {
+            return Err(invalid_data("map cache is stale"));
+        }
+
+        Ok(map)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = BinaryWriter::new();
+        writer.write_bytes(MAGIC);
+        writer.write_u32(CACHE_VERSION);
+        writer.write_u64(self.input_hash);
+
+        self.ground_pathing.write_binary(&mut writer);
+        self.air_pathing.write_binary(&mut writer);
+        self.colossus_pathing.write_binary(&mut writer);
+        self.reaper_pathing.write_binary(&mut writer);
+        writer.write_bool(self.influence_colossus_map);
+        writer.write_bool(self.influence_reaper_map);
+
+        write_points(&mut writer, &self.points);
+        writer.write_grid(&self.placement);
+        writer.write_grid(&self.creep);
+
+        writer.write_usize(self.overlord_spots.len());
+        for spot in &self.overlord_spots {
+            writer.write_point_f32(*spot);
+        }
+
+        writer.write_usize(self.chokes.len());
+        for choke in &self.chokes {
+            write_choke(&mut writer, choke);
+        }
+        self.region_graph.write_binary(&mut writer);
+
+        writer.buffer
+    }
+
+    pub fn from_bytes(data: &[u8]) -> io::Result<Map> {
+        let mut reader = BinaryReader::new(data);
+
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(invalid_data("not a map cache file"));
+        }
+
+        let version = reader.read_u32()?;
+        if version != CACHE_VERSION {
+            return Err(invalid_data(&format!("unsupported map cache version {}", version)));
+        }
+
+        let input_hash = reader.read_u64()?;
+        let ground_pathing = PathFind::read_binary(&mut reader)?;
+        let air_pathing = PathFind::read_binary(&mut reader)?;
+        let colossus_pathing = PathFind::read_binary(&mut reader)?;
+        let reaper_pathing = PathFind::read_binary(&mut reader)?;
+        let influence_colossus_map = reader.read_bool()?;
+        let influence_reaper_map = reader.read_bool()?;
+
+        let points = read_points(&mut reader)?;
+        if points.len() != ground_pathing.width || points[0].len() != ground_pathing.height {
+            return Err(invalid_data("map point grid does not match pathing grid"));
+        }
+
+        let placement = reader.read_grid()?;
+        if placement.len() != ground_pathing.width || placement[0].len() != ground_pathing.height {
+            return Err(invalid_data("placement grid does not match pathing grid"));
+        }
+
+        let creep = reader.read_grid()?;
+        if creep.len() != ground_pathing.width || creep[0].len() != ground_pathing.height {
+            return Err(invalid_data("creep grid does not match pathing grid"));
+        }
+
+        for pathing in &[&air_pathing, &colossus_pathing, &reaper_pathing] {
+            if pathing.width != ground_pathing.width || pathing.height != ground_pathing.height {
+                return Err(invalid_data("pathing grid sizes do not match"));
+            }
+        }
+
+        let count = reader.read_len(8)?;
+        let mut overlord_spots = Vec::with_capacity(count);
+        for _ in 0..count {
+            overlord_spots.push(reader.read_point_f32()?);
+        }
+
+        let count = reader.read_len(1)?;
+        let mut chokes = Vec::with_capacity(count);
+        for _ in 0..count {
+            chokes.push(read_choke(&mut reader)?);
+        }
+
+        let regions = collect_regions(&points, &chokes);
+        let region_graph = RegionGraph::read_binary(&mut reader, regions.len(), chokes.len())?;
+
+        if !reader.is_empty() {
+            return Err(invalid_data("trailing data in map cache"));
+        }
+
+        let ramps = solve_ramps(&points);
+        let jumps_by_takeoff = solve_reaper_jumps(&points);
+
+        Ok(Map { ground_pathing,
+                 air_pathing,
+                 colossus_pathing,
+                 reaper_pathing,
+                 points,
+                 placement,
+                 creep,
+                 overlord_spots,
+                 influence_colossus_map,
+                 influence_reaper_map,
+                 chokes,
+                 regions,
+                 region_graph,
+                 ramps,
+                 jumps_by_takeoff,
+                 input_hash })
+    }
+}
+
+fn write_points(writer: &mut BinaryWriter, points: &[Vec<MapPoint>]) {
+    writer.write_usize(points.len());
+    writer.write_usize(points[0].len());
+
+    for column in points {
+        for point in column {
+            let mut flags = 0u8;
+            if point.pathable {
+                flags |= FLAG_PATHABLE;
+            }
+            if point.walkable {
+                flags |= FLAG_WALKABLE;
+            }
+            if point.climbable {
+                flags |= FLAG_CLIMBABLE;
+            }
+            if point.overlord_spot {
+                flags |= FLAG_OVERLORD_SPOT;
+            }
+            if point.is_border {
+                flags |= FLAG_BORDER;
+            }
+            if point.is_choke {
+                flags |= FLAG_CHOKE;
+            }
+            if point.placeable {
+                flags |= FLAG_PLACEABLE;
+            }
+
+            writer.write_u8(flags);
+            writer.write_u8(point.cliff_type as u8);
+            writer.write_i32(point.zone_index);
+            writer.write_i32(point.structure_index);
+            writer.write_usize(point.height);
+        }
+    }
+}
+
+fn read_points(reader: &mut BinaryReader) -> io::Result<Vec<Vec<MapPoint>>> {
+    let width = reader.read_usize()?;
+    let height = reader.read_usize()?;
+    if width == 0 || height == 0 || width.saturating_mul(height).saturating_mul(8) > reader.remaining() {
+        return Err(invalid_data("invalid map point grid"));
+    }
+
+    let mut points = vec![vec![MapPoint::new(); height]; width];
+    for column in points.iter_mut() {
+        for point in column.iter_mut() {
+            let flags = reader.read_u8()?;
+            point.pathable = flags & FLAG_PATHABLE != 0;
+            point.walkable = flags & FLAG_WALKABLE != 0;
+            point.climbable = flags & FLAG_CLIMBABLE != 0;
+            point.overlord_spot = flags & FLAG_OVERLORD_SPOT != 0;
+            point.is_border = flags & FLAG_BORDER != 0;
+            point.is_choke = flags & FLAG_CHOKE != 0;
+            point.placeable = flags & FLAG_PLACEABLE != 0;
+            point.cliff_type = Cliff::from_bits(reader.read_u8()?).ok_or_else(|| invalid_data("invalid cliff type"))?;
+            point.zone_index = reader.read_i32()?;
+            point.structure_index = reader.read_i32()?;
+            point.height = reader.read_usize()?;
+        }
+    }
+
+    Ok(points)
+}
+
+fn write_choke(writer: &mut BinaryWriter, choke: &Choke) {
+    writer.write_point_f32(choke.main_line.0);
+    writer.write_point_f32(choke.main_line.1);
+    writer.write_usize(choke.lines.len());
+    for line in &choke.lines {
+        writer.write_point(line.0);
+        writer.write_point(line.1);
+    }
+    writer.write_points(&choke.side1);
+    writer.write_points(&choke.side2);
+    writer.write_points(&choke.pixels);
+    writer.write_f32(choke.min_length);
+}
+
+fn read_choke(reader: &mut BinaryReader) -> io::Result<Choke> {
+    let main_line = (reader.read_point_f32()?, reader.read_point_f32()?);
+    let count = reader.read_len(4)?;
+    let mut lines = Vec::with_capacity(count);
+    for _ in 0..count {
+        lines.push((reader.read_point()?, reader.read_point()?));
+    }
+    let side1 = reader.read_points()?;
+    let side2 = reader.read_points()?;
+    let pixels = reader.read_points()?;
+    let min_length = reader.read_f32()?;
+
+    Ok(Choke { main_line,
+               lines,
+               side1,
+               side2,
+               pixels,
+               min_length })
+}
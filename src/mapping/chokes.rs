@@ -3,7 +3,10 @@ use crate::path_find::pos::Pos;
 use crate::path_find::pos::{DIAGONAL_MINUS_CARDINAL, MULT, MULTF32, SQRT2};
 use crate::path_find::PathFind;
 use pathfinding::prelude::absdiff;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::cmp;
 use std::collections::HashSet;
 
@@ -94,8 +97,9 @@ pub fn solve_chokes(points: &mut Vec<Vec<map_point::MapPoint>>,
     }
 }
 
-#[pyclass]
-#[derive(Clone)]
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
 pub struct Choke {
     pub main_line: ((f32, f32), (f32, f32)),
     pub lines: Vec<((usize, usize), (usize, usize))>,
@@ -104,6 +108,8 @@ pub struct Choke {
     pub pixels: Vec<(usize, usize)>,
     pub min_length: f32,
 }
+
+#[cfg(feature = "python")]
 #[pymethods]
 impl Choke {
     #[getter(lines)]
@@ -1,6 +1,6 @@
 use crate::mapping::map_point;
 use crate::mapping::map_point::Cliff;
-const DIFFERENCE: usize = 16;
+use crate::mapping::terrain::LEVEL_DIFFERENCE;
 
 pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32, x_dir: i32, y_dir: i32) {
     let x0 = x as usize;
@@ -29,10 +29,6 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
     let h2 = points[x1][y1].height;
     let h3 = points[x1 + 1][y1].height;
 
-    // Difference between levels is 15.9375 in standard map height maps
-    // Difference between levels is 2 in standard sc2 measurement units.
-    // Because of rounding the height difference needs to be exactly 16
-
     let set_low = |x: Cliff| {
         if x == Cliff::None || x == Cliff::Low {
             Cliff::Low
@@ -53,7 +49,7 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
             // Need to check following scenarios:
             // 10 11 00 01
             // 11 01 10 00
-            if (h0 == h1 || h0 == h2) && h2 == h1 + DIFFERENCE && h0 == h3 {
+            if (h0 == h1 || h0 == h2) && h2 == h1 + LEVEL_DIFFERENCE && h0 == h3 {
                 // 10 00
                 // 11 10
                 points[x1][y1].climbable = true;
@@ -64,7 +60,8 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
                     points[x0][y0].cliff_type = set_low(points[x0][y0].cliff_type);
                     points[x2][y2].cliff_type = set_high(points[x2][y2].cliff_type);
                 }
-            } else if (h0 == h1 && h0 == h3 && h0 == h2 + DIFFERENCE) || (h0 == h2 && h0 == h3 && h1 == h2 + DIFFERENCE)
+            } else if (h0 == h1 && h0 == h3 && h0 == h2 + LEVEL_DIFFERENCE)
+                      || (h0 == h2 && h0 == h3 && h1 == h2 + LEVEL_DIFFERENCE)
             {
                 // 11 01
                 // 01 00
@@ -81,7 +78,9 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
             // Need to check following scenarios:
             // 01 11 10 00
             // 11 10 00 01
-            if (h1 == h2 && h1 == h3 && h1 == h0 + DIFFERENCE) || (h0 == h1 && h0 == h2 && h3 == h0 + DIFFERENCE) {
+            if (h1 == h2 && h1 == h3 && h1 == h0 + LEVEL_DIFFERENCE)
+               || (h0 == h1 && h0 == h2 && h3 == h0 + LEVEL_DIFFERENCE)
+            {
                 // 01 00
                 // 11 01
                 points[x1][y1].climbable = true;
@@ -92,7 +91,8 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
                     points[x0][y0].cliff_type = set_high(points[x0][y0].cliff_type);
                     points[x2][y2].cliff_type = set_low(points[x2][y2].cliff_type);
                 }
-            } else if (h0 == h1 && h0 == h2 && h0 == h3 + DIFFERENCE) || (h1 == h2 && h1 == h3 && h0 == h3 + DIFFERENCE)
+            } else if (h0 == h1 && h0 == h2 && h0 == h3 + LEVEL_DIFFERENCE)
+                      || (h1 == h2 && h1 == h3 && h0 == h3 + LEVEL_DIFFERENCE)
             {
                 // 11 10
                 // 10 00
@@ -111,7 +111,7 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
             // Need to check following scenarios:
             // 01 10
             // 01 10
-            if h0 == h2 && h1 == h3 && h0 + DIFFERENCE == h1 {
+            if h0 == h2 && h1 == h3 && h0 + LEVEL_DIFFERENCE == h1 {
                 // 01
                 // 01
                 points[x1][y1].climbable = true;
@@ -122,7 +122,7 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
                     points[x0][y0].cliff_type = set_high(points[x0][y0].cliff_type);
                     points[x2][y2].cliff_type = set_low(points[x2][y2].cliff_type);
                 }
-            } else if h0 == h2 && h1 == h3 && h0 == h1 + DIFFERENCE {
+            } else if h0 == h2 && h1 == h3 && h0 == h1 + LEVEL_DIFFERENCE {
                 // 10
                 // 10
                 points[x1][y1].climbable = true;
@@ -138,7 +138,7 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
             // Need to check following scenarios:
             // 00 11
             // 11 00
-            if h0 == h1 && h2 == h3 && h0 + DIFFERENCE == h2 {
+            if h0 == h1 && h2 == h3 && h0 + LEVEL_DIFFERENCE == h2 {
                 // 00
                 // 11
                 points[x1][y1].climbable = true;
@@ -149,7 +149,7 @@ pub fn modify_climb(points: &mut Vec<Vec<map_point::MapPoint>>, x: i32, y: i32,
                     points[x0][y0].cliff_type = set_low(points[x0][y0].cliff_type);
                     points[x2][y2].cliff_type = set_high(points[x2][y2].cliff_type);
                 }
-            } else if h0 == h1 && h2 == h3 && h0 == h2 + DIFFERENCE {
+            } else if h0 == h1 && h2 == h3 && h0 == h2 + LEVEL_DIFFERENCE {
                 // 11
                 // 00
                 points[x1][y1].climbable = true;
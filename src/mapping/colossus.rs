@@ -0,0 +1,61 @@
+use crate::mapping::map_point::MapPoint;
+use crate::mapping::terrain::height_to_level;
+
+/// Widest strip of unwalkable cells a colossus steps over, cliff edges are usually 2 cells wide.
+const MAX_GAP: usize = 3;
+/// Highest difference in terrain levels between the sides of a gap that a colossus can climb.
+const MAX_CLIMB: usize = 1;
+
+/// Pathing grid for colossi, ground pathing plus the unwalkable cells they can walk over.
+///
+/// A cell can be crossed when it is in the playable area and part of a gap of at most `MAX_GAP` cells,
+/// horizontally, vertically or diagonally, between walkable ground at most `MAX_CLIMB` levels apart.
+/// `walkable`, `pathable` and `height` of the map points must be set.
+pub fn solve_colossus_map(points: &[Vec<MapPoint>]) -> Vec<Vec<usize>> {
+    points.iter()
+          .enumerate()
+          .map(|(x, column)| {
+              column.iter()
+                    .enumerate()
+                    .map(|(y, point)| (point.walkable || point.pathable && is_crossable(points, (x, y))) as usize)
+                    .collect()
+          })
+          .collect()
+}
+
+fn is_crossable(points: &[Vec<MapPoint>], pos: (usize, usize)) -> bool {
+    for &(dx, dy) in &[(1, 0), (0, 1), (1, 1), (1, -1)] {
+        let forward = ground_along(points, pos, (dx, dy));
+        let backward = ground_along(points, pos, (-dx, -dy));
+
+        if let (Some((steps_a, height_a)), Some((steps_b, height_b))) = (forward, backward) {
+            let (level_a, level_b) = (height_to_level(height_a), height_to_level(height_b));
+            let climb = level_a.max(level_b) - level_a.min(level_b);
+            if steps_a + steps_b - 1 <= MAX_GAP && climb <= MAX_CLIMB {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Steps to the closest walkable cell in the direction and its height, `None` when it is further than `MAX_GAP`
+/// or the way leaves the playable area.
+fn ground_along(points: &[Vec<MapPoint>], pos: (usize, usize), direction: (isize, isize)) -> Option<(usize, usize)> {
+    let (mut x, mut y) = (pos.0 as isize, pos.1 as isize);
+
+    for steps in 1..=MAX_GAP {
+        x += direction.0;
+        y += direction.1;
+        let point = points.get(x as usize)?.get(y as usize)?;
+        if !point.pathable {
+            return None;
+        }
+        if point.walkable {
+            return Some((steps, point.height));
+        }
+    }
+
+    None
+}
@@ -0,0 +1,109 @@
+use crate::error::{Error, Result};
+use crate::mapping::map::Map;
+use crate::mapping::mappings::MapType;
+#[cfg(feature = "python")]
+use crate::mapping::mappings::map_type_py;
+#[cfg(feature = "python")]
+use crate::numpy_grid::{extract_grid, grid_to_array, Orientation};
+use crate::path_find::rectangle::Rectangle;
+#[cfg(feature = "python")]
+use numpy::PyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Speed of most ground units on creep compared to off creep.
+pub const CREEP_SPEED: f32 = 1.3;
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    #[getter(creep)]
+    fn get_creep<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.creep, Orientation::XY)
+    }
+
+    /// Replaces the creep layer, any value above 0 is creep.
+    #[name = "set_creep"]
+    #[args(orientation = "\"xy\"")]
+    fn set_creep_py(&mut self, creep: &PyAny, orientation: &str) -> PyResult<()> {
+        let creep = extract_grid(creep, Orientation::parse(orientation)?)?;
+        Ok(self.set_creep(creep)?)
+    }
+
+    /// Whether a building of the size fits at the position with creep under all of it.
+    #[name = "on_creep"]
+    fn on_creep_py(&self, size: (usize, usize), center: (f32, f32)) -> bool { self.on_creep(size, center) }
+
+    /// Fastest path for a unit that moves `creep_speed` times faster on creep, returns the path and the distance
+    /// the unit would cover off creep in the same time.
+    #[name = "find_path_creep"]
+    #[args(creep_speed = "1.3")]
+    fn find_path_creep_py(&self,
+                          py: Python,
+                          map_type: u8,
+                          start: (f32, f32),
+                          end: (f32, f32),
+                          creep_speed: f32)
+                          -> PyResult<(Vec<(usize, usize)>, f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_path_creep(map_type, start, end, creep_speed)))
+    }
+}
+
+impl Map {
+    /// Replaces the creep layer with a grid of the map size, any value above 0 is creep.
+    pub fn set_creep(&mut self, creep: Vec<Vec<usize>>) -> Result<()> {
+        let size = (self.ground_pathing.width, self.ground_pathing.height);
+        if creep.len() != size.0 || creep.iter().any(|column| column.len() != size.1) {
+            return Err(Error::GridSizeMismatch { expected: size,
+                                                 found: (creep.len(), creep.first().map_or(0, |c| c.len())) });
+        }
+
+        self.creep = creep.into_iter()
+                          .map(|column| column.into_iter().map(|value| (value > 0) as usize).collect())
+                          .collect();
+        Ok(())
+    }
+
+    pub fn has_creep(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.ground_pathing.width && pos.1 < self.ground_pathing.height && self.creep[pos.0][pos.1] > 0
+    }
+
+    /// Whether there is creep under every cell of a building of the size at the position.
+    pub fn on_creep(&self, size: (usize, usize), center: (f32, f32)) -> bool {
+        if center.0 < 0.0 || center.1 < 0.0 {
+            return false;
+        }
+
+        let rect = Rectangle::init_from_center(center, size, self.ground_pathing.width, self.ground_pathing.height);
+        rect.x_end - rect.x == size.0 && rect.y_end - rect.y == size.1 && self.is_on_creep(&rect)
+    }
+
+    pub(crate) fn is_on_creep(&self, rect: &Rectangle) -> bool {
+        self.creep[rect.x..rect.x_end].iter().all(|column| column[rect.y..rect.y_end].iter().all(|cell| *cell > 0))
+    }
+
+    /// Speed multiplier of every cell for a unit that moves `creep_speed` times faster on creep.
+    pub fn creep_speed_grid(&self, creep_speed: f32) -> Vec<Vec<f32>> {
+        self.creep
+            .iter()
+            .map(|column| column.iter().map(|cell| if *cell > 0 { creep_speed } else { 1.0 }).collect())
+            .collect()
+    }
+
+    /// Fastest path for a unit that moves `creep_speed` times faster on creep.
+    ///
+    /// The returned distance is the time the path takes, as the distance the unit would cover off creep.
+    pub fn find_path_creep(&self,
+                           map_type: MapType,
+                           start: (f32, f32),
+                           end: (f32, f32),
+                           creep_speed: f32)
+                           -> (Vec<(usize, usize)>, f32) {
+        let start_int = (start.0.round() as usize, start.1.round() as usize);
+        let end_int = (end.0.round() as usize, end.1.round() as usize);
+        let speed = self.creep_speed_grid(creep_speed);
+
+        self.get_map(map_type).find_path_speed(start_int, end_int, &speed)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::mapping::map::Map;
+use crate::path_find::rectangle::Rectangle;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Resources closer than this to any resource of a group belong to the same base.
+const RESOURCE_SPREAD: f32 = 8.5;
+/// Town halls can't be placed within this many cells of minerals or geysers.
+const RESOURCE_GAP: isize = 3;
+/// Distance from the middle of the resources searched for the town hall.
+const SEARCH_RADIUS: isize = 10;
+
+const TOWN_HALL_SIZE: (usize, usize) = (5, 5);
+const MINERAL_SIZE: (usize, usize) = (2, 1);
+const GEYSER_SIZE: (usize, usize) = (3, 3);
+
+/// Town hall position for a group of resources.
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expansion {
+    /// Center of the town hall
+    pub center: (f32, f32),
+    pub minerals: Vec<(f32, f32)>,
+    pub geysers: Vec<(f32, f32)>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Expansion {
+    #[getter(center)]
+    fn get_center(&self) -> (f32, f32) { self.center }
+
+    #[getter(minerals)]
+    fn get_minerals(&self) -> Vec<(f32, f32)> { self.minerals.clone() }
+
+    #[getter(geysers)]
+    fn get_geysers(&self) -> Vec<(f32, f32)> { self.geysers.clone() }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Town hall positions for the mineral fields and vespene geysers of the map, positions are unit centers.
+    #[name = "expansion_locations"]
+    fn expansion_locations_py(&self,
+                              py: Python,
+                              minerals: Vec<(f32, f32)>,
+                              geysers: Vec<(f32, f32)>)
+                              -> Vec<Expansion> {
+        py.allow_threads(|| self.expansion_locations(&minerals, &geysers))
+    }
+}
+
+impl Map {
+    /// Groups the resources into bases and finds the town hall position of each base.
+    ///
+    /// The town hall is placed on placeable cells at least 3 cells away from the resources of its group,
+    /// as close as possible to all of them. Single resources and groups without a valid position are left out.
+    pub fn expansion_locations(&self, minerals: &[(f32, f32)], geysers: &[(f32, f32)]) -> Vec<Expansion> {
+        let resources: Vec<((f32, f32), bool)> = minerals.iter()
+                                                         .map(|pos| (*pos, false))
+                                                         .chain(geysers.iter().map(|pos| (*pos, true)))
+                                                         .collect();
+        let mut expansions = Vec::new();
+
+        for group in group_resources(&resources) {
+            if group.len() < 2 {
+                continue;
+            }
+
+            let group: Vec<((f32, f32), bool)> = group.iter().map(|index| resources[*index]).collect();
+            if let Some(center) = self.town_hall_position(&group) {
+                let of_kind = |geyser: bool| {
+                    group.iter()
+                         .filter(|(_, is_geyser)| *is_geyser == geyser)
+                         .map(|(pos, _)| *pos)
+                         .collect()
+                };
+                expansions.push(Expansion { center,
+                                            minerals: of_kind(false),
+                                            geysers: of_kind(true) });
+            }
+        }
+
+        expansions
+    }
+
+    fn town_hall_position(&self, group: &[((f32, f32), bool)]) -> Option<(f32, f32)> {
+        let width = self.points.len();
+        let height = self.points[0].len();
+        let count = group.len() as f32;
+        let middle = (group.iter().map(|(pos, _)| pos.0).sum::<f32>() / count,
+                      group.iter().map(|(pos, _)| pos.1).sum::<f32>() / count);
+        let footprints: Vec<Rectangle> = group.iter()
+                                              .map(|(pos, geyser)| {
+                                                  let size = if *geyser { GEYSER_SIZE } else { MINERAL_SIZE };
+                                                  Rectangle::init_from_center(*pos, size, width, height)
+                                              })
+                                              .collect();
+
+        let mut best = None;
+        let mut best_score = f32::MAX;
+
+        for dx in -SEARCH_RADIUS..=SEARCH_RADIUS {
+            for dy in -SEARCH_RADIUS..=SEARCH_RADIUS {
+                let x = middle.0 as isize + dx;
+                let y = middle.1 as isize + dy;
+                if x < 0 || y < 0 {
+                    continue;
+                }
+
+                let center = (x as f32 + 0.5, y as f32 + 0.5);
+                let town_hall = Rectangle::init_from_center(center, TOWN_HALL_SIZE, width, height);
+                if !self.is_placeable(&town_hall, TOWN_HALL_SIZE)
+                   || footprints.iter().any(|footprint| town_hall.gap(footprint) < RESOURCE_GAP)
+                {
+                    continue;
+                }
+
+                let score: f32 = group.iter()
+                                      .map(|(pos, _)| ((pos.0 - center.0).powi(2) + (pos.1 - center.1).powi(2)).sqrt())
+                                      .sum();
+                if score < best_score {
+                    best = Some(center);
+                    best_score = score;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Every cell of the rectangle is placeable, rectangles cut by the map edge are not.
+    fn is_placeable(&self, rect: &Rectangle, size: (usize, usize)) -> bool {
+        if rect.x_end - rect.x != size.0 || rect.y_end - rect.y != size.1 {
+            return false;
+        }
+
+        self.points[rect.x..rect.x_end].iter()
+                                       .all(|column| column[rect.y..rect.y_end].iter().all(|point| point.placeable))
+    }
+}
+
+/// Indices of the resources in each group, resources are joined when they are within `RESOURCE_SPREAD` of each other.
+fn group_resources(resources: &[((f32, f32), bool)]) -> Vec<Vec<usize>> {
+    let mut grouped = vec![false; resources.len()];
+    let mut groups = Vec::new();
+
+    for start in 0..resources.len() {
+        if grouped[start] {
+            continue;
+        }
+
+        let mut group = vec![start];
+        grouped[start] = true;
+        let mut next = 0;
+        while next < group.len() {
+            let (pos, _) = resources[group[next]];
+            for (index, (other, _)) in resources.iter().enumerate() {
+                let distance = ((pos.0 - other.0).powi(2) + (pos.1 - other.1).powi(2)).sqrt();
+                if !grouped[index] && distance <= RESOURCE_SPREAD {
+                    grouped[index] = true;
+                    group.push(index);
+                }
+            }
+            next += 1;
+        }
+
+        groups.push(group);
+    }
+
+    groups
+}
@@ -1,6 +1,7 @@
 use crate::path_find::pos::Pos;
 use crate::path_find::pos::{self, MULTF32};
 use crate::path_find::{octile_distance, rectangle, PathFind};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 // extern crate test;
@@ -8,16 +9,10 @@ use std::collections::HashSet;
 
 use super::chokes::{group_chokes, Choke};
 
-const DIFFERENCE: usize = 16;
 const Y_MULT: usize = 1000000;
 use crate::mapping::map::Map;
+use crate::mapping::mappings::MapsType;
 
-const MAPS_PURE_GROUND: usize = 0;
-const MAPS_GROUND: usize = 1;
-const MAPS_AIR: usize = 2;
-const MAPS_BOTH: usize = 3;
-
-#[pymethods]
 impl Map {
     pub fn normalize_influence(&mut self, value: usize) {
         self.ground_pathing.normalize_influence(value);
@@ -26,12 +21,12 @@ impl Map {
         self.reaper_pathing.normalize_influence(value);
     }
 
-    pub fn add_influence_walk(&mut self, positions: Vec<(f32, f32)>, influence: f32, distance: f32) {
+    pub fn add_influence_walk(&mut self, positions: &[(f32, f32)], influence: f32, distance: f32) {
         let mult = 1.0 / distance;
         let max_int = influence as usize;
         let mut maps = self.get_ground_influence_maps();
 
-        for position_f in &positions {
+        for position_f in positions {
             let position = (position_f.0.round() as usize, position_f.1.round() as usize);
             if maps[0].map[position.0][position.1] == 0 {
                 continue;
@@ -54,7 +49,7 @@ impl Map {
         }
     }
 
-    pub fn add_influence_flat_hollow(&mut self, positions: Vec<(f32, f32)>, influence: f32, min: f32, max: f32) {
+    pub fn add_influence_flat_hollow(&mut self, positions: &[(f32, f32)], influence: f32, min: f32, max: f32) {
         let value = influence as usize;
         let mult_min = min * pos::MULTF32;
         let mult_max = max * pos::MULTF32;
@@ -64,7 +59,7 @@ impl Map {
         let diameter = ((max * 2f32) as usize) + 2;
         let rect_size = (diameter, diameter);
 
-        for position_f in &positions {
+        for position_f in positions {
             let position = (position_f.0.round() as usize, position_f.1.round() as usize);
             let rect = rectangle::Rectangle::init_from_center2(position, rect_size, maps[0].width, maps[0].height);
 
@@ -85,8 +80,8 @@ impl Map {
     }
 
     pub fn add_influence_fading(&mut self,
-                                map_type: usize,
-                                positions: Vec<(f32, f32)>,
+                                maps_type: MapsType,
+                                positions: &[(f32, f32)],
                                 influence: f32,
                                 min: f32,
                                 max: f32) {
@@ -95,22 +90,17 @@ impl Map {
         let value = influence as usize;
         let mult_min = min * pos::MULTF32;
         let mult_max = max * pos::MULTF32;
-        let mut maps: Vec<&mut PathFind>;
-
-        if map_type == MAPS_PURE_GROUND {
-            maps = self.get_pure_ground_influence_maps();
-        } else if map_type == MAPS_GROUND {
-            maps = self.get_ground_influence_maps();
-        } else if map_type == MAPS_AIR {
-            maps = self.get_air_influence_maps();
-        } else {
-            maps = self.get_both_influence_maps();
-        }
+        let mut maps = match maps_type {
+            MapsType::PureGround => self.get_pure_ground_influence_maps(),
+            MapsType::Ground => self.get_ground_influence_maps(),
+            MapsType::Air => self.get_air_influence_maps(),
+            MapsType::Both => self.get_both_influence_maps(),
+        };
 
         let diameter = ((max * 2f32) as usize) + 2;
         let rect_size = (diameter, diameter);
 
-        for position_f in &positions {
+        for position_f in positions {
             let position = (position_f.0.round() as usize, position_f.1.round() as usize);
             let rect = rectangle::Rectangle::init_from_center2(position, rect_size, maps[0].width, maps[0].height);
 
@@ -142,6 +132,41 @@ impl Map {
     }
 }
 
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    #[name = "normalize_influence"]
+    fn normalize_influence_py(&mut self, value: usize) { self.normalize_influence(value); }
+
+    #[name = "add_influence_walk"]
+    fn add_influence_walk_py(&mut self, py: Python, positions: Vec<(f32, f32)>, influence: f32, distance: f32) {
+        py.allow_threads(|| self.add_influence_walk(&positions, influence, distance));
+    }
+
+    #[name = "add_influence_flat_hollow"]
+    fn add_influence_flat_hollow_py(&mut self,
+                                    py: Python,
+                                    positions: Vec<(f32, f32)>,
+                                    influence: f32,
+                                    min: f32,
+                                    max: f32) {
+        py.allow_threads(|| self.add_influence_flat_hollow(&positions, influence, min, max));
+    }
+
+    /// Unknown map types add influence to all maps as before.
+    #[name = "add_influence_fading"]
+    fn add_influence_fading_py(&mut self,
+                               py: Python,
+                               map_type: usize,
+                               positions: Vec<(f32, f32)>,
+                               influence: f32,
+                               min: f32,
+                               max: f32) {
+        let maps_type = MapsType::from_usize(map_type).unwrap_or(MapsType::Both);
+        py.allow_threads(|| self.add_influence_fading(maps_type, &positions, influence, min, max));
+    }
+}
+
 impl Map {
     fn get_both_influence_maps(&mut self) -> Vec<&mut PathFind> {
         let mut maps = Vec::<&mut PathFind>::new();
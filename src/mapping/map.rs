@@ -1,67 +1,283 @@
+#[cfg(feature = "python")]
+use crate::mapping::mappings::map_type_py;
+use crate::mapping::mappings::MapType;
+#[cfg(feature = "python")]
+use crate::numpy_grid::{extract_grid, grid_to_array, Orientation};
+use crate::path_find::footprint::Footprint;
+use crate::path_find::obstacles::Obstacle;
+#[cfg(feature = "python")]
+use crate::path_find::footprint::extract_footprint;
 use crate::path_find::PathFind;
+use crate::error::{Error, Result};
+#[cfg(feature = "python")]
+use numpy::PyArray2;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 // extern crate test;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use super::chokes::{group_chokes, Choke};
+use crate::mapping::cache::input_hash;
 use crate::mapping::chokes::solve_chokes;
 use crate::mapping::climb::modify_climb;
+use crate::mapping::colossus::solve_colossus_map;
 use crate::mapping::map_point;
 use crate::mapping::map_point::Cliff;
+use crate::mapping::placement::original_placement;
+use crate::mapping::ramps::{solve_ramps, Ramp};
+use crate::mapping::reaper::{solve_reaper_jumps, ReaperJump};
+use crate::mapping::region_graph::RegionGraph;
+use crate::mapping::regions::{solve_regions, Region};
+use crate::mapping::terrain::LEVEL_DIFFERENCE;
 
-const DIFFERENCE: usize = 16;
 const Y_MULT: usize = 1000000;
 
 /// Mapping for python-sc2
-#[pyclass]
+#[cfg_attr(feature = "python", pyclass)]
 pub struct Map {
     pub ground_pathing: PathFind,
     pub air_pathing: PathFind,
     pub colossus_pathing: PathFind,
     pub reaper_pathing: PathFind,
     pub points: Vec<Vec<map_point::MapPoint>>,
+    /// Cells where buildings can currently be placed, 1 for placeable, kept up to date by the block functions
+    pub placement: Vec<Vec<usize>>,
+    /// Cells covered by creep, 1 for creep, updated with `set_creep`
+    pub creep: Vec<Vec<usize>>,
     pub overlord_spots: Vec<(f32, f32)>,
-    #[pyo3(get, set)]
     pub influence_colossus_map: bool,
-    #[pyo3(get, set)]
     pub influence_reaper_map: bool,
     pub chokes: Vec<Choke>,
+    pub regions: Vec<Region>,
+    pub region_graph: RegionGraph,
+    pub ramps: Vec<Ramp>,
+    /// Reaper jumps by takeoff cell, including jumps from or to cells that are currently blocked
+    pub jumps_by_takeoff: HashMap<(usize, usize), Vec<ReaperJump>>,
+    pub input_hash: u64,
 }
 
+#[cfg(feature = "python")]
 #[pymethods]
 impl Map {
+    /// Grids can be numpy arrays or nested lists, `orientation` is either "xy" for `grid[x][y]`
+    /// or "yx" for `grid[y][x]` as received from the game.
     #[new]
-    fn new_py(pathing: Vec<Vec<usize>>,
-              placement: Vec<Vec<usize>>,
-              height_map: Vec<Vec<usize>>,
+    #[args(orientation = "\"xy\"")]
+    #[allow(clippy::too_many_arguments)]
+    fn new_py(py: Python,
+              pathing: &PyAny,
+              placement: &PyAny,
+              height_map: &PyAny,
               x_start: usize,
               y_start: usize,
               x_end: usize,
-              y_end: usize)
-              -> Self {
-        Map::new(pathing, placement, height_map, x_start, y_start, x_end, y_end)
+              y_end: usize,
+              orientation: &str)
+              -> PyResult<Self> {
+        let orientation = Orientation::parse(orientation)?;
+        let pathing = extract_grid(pathing, orientation)?;
+        let placement = extract_grid(placement, orientation)?;
+        let height_map = extract_grid(height_map, orientation)?;
+        Ok(py.allow_threads(|| Map::new(pathing, placement, height_map, x_start, y_start, x_end, y_end))?)
     }
 
     #[getter(ground_pathing)]
-    fn get_ground_pathing(&self) -> Vec<Vec<usize>> { self.ground_pathing.map.clone() }
+    fn get_ground_pathing<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.ground_pathing.map, Orientation::XY)
+    }
 
     #[getter(air_pathing)]
-    fn get_air_pathing(&self) -> Vec<Vec<usize>> { self.air_pathing.map.clone() }
+    fn get_air_pathing<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.air_pathing.map, Orientation::XY)
+    }
 
     #[getter(reaper_pathing)]
-    fn get_reaper_pathing(&self) -> Vec<Vec<usize>> { self.reaper_pathing.map.clone() }
+    fn get_reaper_pathing<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.reaper_pathing.map, Orientation::XY)
+    }
 
     #[getter(colossus_pathing)]
-    fn get_colossus_pathing(&self) -> Vec<Vec<usize>> { self.colossus_pathing.map.clone() }
+    fn get_colossus_pathing<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.colossus_pathing.map, Orientation::XY)
+    }
+
+    /// Read-only copy of the selected pathing map as numpy array in the requested orientation.
+    #[args(orientation = "\"xy\"")]
+    fn pathing_array<'py>(&self, py: Python<'py>, map_type: u8, orientation: &str) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.get_map(map_type_py(map_type)?).map, Orientation::parse(orientation)?)
+    }
+
+    #[getter(placement)]
+    fn get_placement<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.placement, Orientation::XY)
+    }
+
+    #[getter(influence_colossus_map)]
+    fn get_influence_colossus_map(&self) -> bool { self.influence_colossus_map }
+
+    #[setter(influence_colossus_map)]
+    fn set_influence_colossus_map(&mut self, value: bool) { self.influence_colossus_map = value; }
+
+    #[getter(influence_reaper_map)]
+    fn get_influence_reaper_map(&self) -> bool { self.influence_reaper_map }
+
+    #[setter(influence_reaper_map)]
+    fn set_influence_reaper_map(&mut self, value: bool) { self.influence_reaper_map = value; }
 
     #[getter(overlord_spots)]
     fn get_overlord_spots(&self) -> Vec<(f32, f32)> { self.overlord_spots.clone() }
 
     #[getter(chokes)]
-    pub fn get_chokes(&self) -> Vec<Choke> { self.chokes.clone() }
+    fn get_chokes(&self) -> Vec<Choke> { self.chokes.clone() }
+
+    #[name = "draw_climbs"]
+    fn draw_climbs_py(&self) -> Vec<Vec<usize>> { self.draw_climbs() }
+
+    #[name = "draw_chokes"]
+    fn draw_chokes_py(&self) -> Vec<Vec<usize>> { self.draw_chokes() }
+
+    /// Reset all mapping to their originals.
+    #[name = "reset"]
+    fn reset_py(&mut self) { self.reset(); }
+
+    #[name = "create_block"]
+    fn create_block_py(&mut self, center: (f32, f32), size: (usize, usize)) { self.create_block(center, size); }
+
+    #[name = "create_blocks"]
+    fn create_blocks_py(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
+        self.create_blocks(&centers, size);
+    }
+
+    #[name = "remove_block"]
+    fn remove_block_py(&mut self, center: (f32, f32), size: (usize, usize)) { self.remove_block(center, size); }
+
+    #[name = "remove_blocks"]
+    fn remove_blocks_py(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
+        self.remove_blocks(&centers, size);
+    }
+
+    /// Blocks a shape, either a name from `Footprint.names()` or a `Footprint`.
+    #[name = "create_footprint_block"]
+    fn create_footprint_block_py(&mut self, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.create_footprint_block(&extract_footprint(shape)?, center);
+        Ok(())
+    }
+
+    #[name = "remove_footprint_block"]
+    fn remove_footprint_block_py(&mut self, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.remove_footprint_block(&extract_footprint(shape)?, center);
+        Ok(())
+    }
+
+    /// Blocks a shape under the id on the ground maps and the placement layer, see `PathFind.add_obstacle`.
+    #[name = "add_obstacle"]
+    fn add_obstacle_py(&mut self, id: u64, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.add_obstacle(id, extract_footprint(shape)?, center);
+        Ok(())
+    }
+
+    #[name = "remove_obstacle"]
+    fn remove_obstacle_py(&mut self, id: u64) -> bool { self.remove_obstacle(id).is_some() }
+
+    #[getter(obstacles)]
+    fn get_obstacles(&self) -> Vec<Obstacle> { self.obstacles().cloned().collect() }
+
+    #[name = "get_borders"]
+    fn get_borders_py(&self) -> Vec<(usize, usize)> { self.get_borders() }
+
+    /// Finds the first reachable position within specified walking distance from the center point with lowest value
+    #[name = "lowest_influence_walk"]
+    fn lowest_influence_walk_py(&self,
+                                py: Python,
+                                map_type: u8,
+                                center: (f32, f32),
+                                distance: f32)
+                                -> PyResult<((usize, usize), f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.lowest_influence_walk(map_type, center, distance)))
+    }
+
+    /// Finds the first reachable position within specified distance from the center point with lowest value
+    #[name = "lowest_influence"]
+    fn lowest_influence_py(&self,
+                           py: Python,
+                           map_type: u8,
+                           center: (f32, f32),
+                           distance: usize)
+                           -> PyResult<((usize, usize), f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.lowest_influence(map_type, center, distance)))
+    }
+
+    /// Find the shortest path values without considering influence and returns the path and distance
+    #[name = "find_path"]
+    fn find_path_py(&self,
+                    py: Python,
+                    map_type: u8,
+                    start: (f32, f32),
+                    end: (f32, f32),
+                    possible_heuristic: Option<u8>)
+                    -> PyResult<(Vec<(usize, usize)>, f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_path(map_type, start, end, possible_heuristic)))
+    }
+
+    /// Find the shortest path values without considering influence and returns the path and distance
+    #[name = "find_path_large"]
+    fn find_path_large_py(&self,
+                          py: Python,
+                          map_type: u8,
+                          start: (f32, f32),
+                          end: (f32, f32),
+                          possible_heuristic: Option<u8>)
+                          -> PyResult<(Vec<(usize, usize)>, f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_path_large(map_type, start, end, possible_heuristic)))
+    }
+
+    /// Find the path using influence values and returns the path and distance
+    #[name = "find_path_influence"]
+    fn find_path_influence_py(&self,
+                              py: Python,
+                              map_type: u8,
+                              start: (f32, f32),
+                              end: (f32, f32),
+                              possible_heuristic: Option<u8>)
+                              -> PyResult<(Vec<(usize, usize)>, f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_path_influence(map_type, start, end, possible_heuristic)))
+    }
+
+    /// Find the path using influence values and returns the path and distance
+    #[name = "find_path_influence_large"]
+    fn find_path_influence_large_py(&self,
+                                    py: Python,
+                                    map_type: u8,
+                                    start: (f32, f32),
+                                    end: (f32, f32),
+                                    possible_heuristic: Option<u8>)
+                                    -> PyResult<(Vec<(usize, usize)>, f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_path_influence_large(map_type, start, end, possible_heuristic)))
+    }
+
+    /// Finds a compromise where low influence matches with close position to the start position.
+    #[name = "find_low_inside_walk"]
+    fn find_low_inside_walk_py(&self,
+                               py: Python,
+                               map_type: u8,
+                               start: (f32, f32),
+                               target: (f32, f32),
+                               distance: f32)
+                               -> PyResult<((f32, f32), f32)> {
+        let map_type = map_type_py(map_type)?;
+        Ok(py.allow_threads(|| self.find_low_inside_walk(map_type, start, target, distance)))
+    }
+}
 
-    fn draw_climbs(&self) -> Vec<Vec<usize>> {
+impl Map {
+    pub fn draw_climbs(&self) -> Vec<Vec<usize>> {
         let width = self.ground_pathing.map.len();
         let height = self.ground_pathing.map[0].len();
         let mut walk_map = vec![vec![0; height]; width];
@@ -90,7 +306,7 @@ impl Map {
         walk_map
     }
 
-    fn draw_chokes(&self) -> Vec<Vec<usize>> {
+    pub fn draw_chokes(&self) -> Vec<Vec<usize>> {
         let width = self.ground_pathing.map.len();
         let height = self.ground_pathing.map[0].len();
         let mut walk_map = vec![vec![0; height]; width];
@@ -115,30 +331,104 @@ impl Map {
 
     /// Reset all mapping to their originals.
     pub fn reset(&mut self) {
-        self.ground_pathing.reset_void();
-        self.air_pathing.reset_void();
-        self.colossus_pathing.reset_void();
-        self.reaper_pathing.reset_void();
+        self.ground_pathing.reset();
+        self.air_pathing.reset();
+        self.colossus_pathing.reset();
+        self.reaper_pathing.reset();
+        self.placement = original_placement(&self.points);
+        for (x, column) in self.placement.iter_mut().enumerate() {
+            for (y, cell) in column.iter_mut().enumerate() {
+                if self.ground_pathing.is_placement_obstacle((x, y)) {
+                    *cell = 0;
+                }
+            }
+        }
     }
 
     pub fn create_block(&mut self, center: (f32, f32), size: (usize, usize)) {
         self.ground_pathing.create_block(center, size);
         self.colossus_pathing.create_block(center, size);
         self.reaper_pathing.create_block(center, size);
+        self.set_placement(center, size, false);
+    }
+
+    pub fn create_blocks(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
+        self.ground_pathing.create_blocks(centers, size);
+        self.colossus_pathing.create_blocks(centers, size);
+        self.reaper_pathing.create_blocks(centers, size);
+        for center in centers {
+            self.set_placement(*center, size, false);
+        }
+    }
+
+    pub fn remove_block(&mut self, center: (f32, f32), size: (usize, usize)) {
+        self.ground_pathing.remove_block(center, size);
+        self.colossus_pathing.remove_block(center, size);
+        self.reaper_pathing.remove_block(center, size);
+        self.set_placement(center, size, true);
+    }
+
+    pub fn remove_blocks(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
+        self.ground_pathing.remove_blocks(centers, size);
+        self.colossus_pathing.remove_blocks(centers, size);
+        self.reaper_pathing.remove_blocks(centers, size);
+        for center in centers {
+            self.set_placement(*center, size, true);
+        }
+    }
+
+    /// Blocks the pathing cells of the shape on the ground maps and its placement cells on the placement layer.
+    pub fn create_footprint_block(&mut self, footprint: &Footprint, center: (f32, f32)) {
+        self.ground_pathing.create_footprint_block(footprint, center);
+        self.colossus_pathing.create_footprint_block(footprint, center);
+        self.reaper_pathing.create_footprint_block(footprint, center);
+        let (width, height) = (self.ground_pathing.width, self.ground_pathing.height);
+        for (x, y) in footprint.cells(&footprint.placement, center, width, height) {
+            self.placement[x][y] = 0;
+        }
+    }
+
+    pub fn remove_footprint_block(&mut self, footprint: &Footprint, center: (f32, f32)) {
+        self.ground_pathing.remove_footprint_block(footprint, center);
+        self.colossus_pathing.remove_footprint_block(footprint, center);
+        self.reaper_pathing.remove_footprint_block(footprint, center);
+        let (width, height) = (self.ground_pathing.width, self.ground_pathing.height);
+        for (x, y) in footprint.cells(&footprint.placement, center, width, height) {
+            self.restore_placement(x, y);
+        }
     }
 
-    pub fn create_blocks(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
-        self.ground_pathing.create_blocks_rust(&centers, size);
-        self.colossus_pathing.create_blocks_rust(&centers, size);
-        self.reaper_pathing.create_blocks_rust(&centers, size);
+    /// Blocks the footprint on the ground maps and the placement layer until the obstacle is removed.
+    pub fn add_obstacle(&mut self, id: u64, footprint: Footprint, center: (f32, f32)) {
+        self.remove_obstacle(id);
+        self.colossus_pathing.add_obstacle(id, footprint.clone(), center);
+        self.reaper_pathing.add_obstacle(id, footprint.clone(), center);
+        self.ground_pathing.add_obstacle(id, footprint, center);
+        let (width, height) = (self.ground_pathing.width, self.ground_pathing.height);
+        if let Some(obstacle) = self.ground_pathing.obstacles().find(|obstacle| obstacle.id == id) {
+            for (x, y) in obstacle.placement_cells(width, height) {
+                self.placement[x][y] = 0;
+            }
+        }
     }
 
-    pub fn remove_blocks(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
-        self.ground_pathing.remove_blocks_rust(&centers, size);
-        self.colossus_pathing.remove_blocks_rust(&centers, size);
-        self.reaper_pathing.remove_blocks_rust(&centers, size);
+    /// Removes the obstacle with the id, cells covered by other obstacles stay blocked.
+    pub fn remove_obstacle(&mut self, id: u64) -> Option<Obstacle> {
+        self.colossus_pathing.remove_obstacle(id);
+        self.reaper_pathing.remove_obstacle(id);
+        let removed = self.ground_pathing.remove_obstacle(id)?;
+
+        let (width, height) = (self.ground_pathing.width, self.ground_pathing.height);
+        for (x, y) in removed.placement_cells(width, height) {
+            self.restore_placement(x, y);
+        }
+
+        Some(removed)
     }
 
+    /// Current obstacles in the order of their ids.
+    pub fn obstacles(&self) -> impl Iterator<Item = &Obstacle> { self.ground_pathing.obstacles() }
+
     pub fn get_borders(&self) -> Vec<(usize, usize)> {
         let mut result = Vec::<(usize, usize)>::new();
 
@@ -154,7 +444,7 @@ impl Map {
     }
 
     /// Finds the first reachable position within specified walking distance from the center point with lowest value
-    fn lowest_influence_walk(&self, map_type: u8, center: (f32, f32), distance: f32) -> ((usize, usize), f32) {
+    pub fn lowest_influence_walk(&self, map_type: MapType, center: (f32, f32), distance: f32) -> ((usize, usize), f32) {
         let map = self.get_map(map_type);
         let center_int = (center.0.round() as usize, center.1.round() as usize);
 
@@ -162,14 +452,14 @@ impl Map {
     }
 
     /// Finds the first reachable position within specified distance from the center point with lowest value
-    pub fn lowest_influence(&self, map_type: u8, center: (f32, f32), distance: usize) -> ((usize, usize), f32) {
+    pub fn lowest_influence(&self, map_type: MapType, center: (f32, f32), distance: usize) -> ((usize, usize), f32) {
         let map = self.get_map(map_type);
         return map.inline_lowest_value(center, distance);
     }
 
     /// Find the shortest path values without considering influence and returns the path and distance
     pub fn find_path(&self,
-                     map_type: u8,
+                     map_type: MapType,
                      start: (f32, f32),
                      end: (f32, f32),
                      possible_heuristic: Option<u8>)
@@ -183,7 +473,7 @@ impl Map {
 
     /// Find the shortest path values without considering influence and returns the path and distance
     pub fn find_path_large(&self,
-                           map_type: u8,
+                           map_type: MapType,
                            start: (f32, f32),
                            end: (f32, f32),
                            possible_heuristic: Option<u8>)
@@ -197,7 +487,7 @@ impl Map {
 
     /// Find the path using influence values and returns the path and distance
     pub fn find_path_influence(&self,
-                               map_type: u8,
+                               map_type: MapType,
                                start: (f32, f32),
                                end: (f32, f32),
                                possible_heuristic: Option<u8>)
@@ -210,7 +500,7 @@ impl Map {
 
     /// Find the path using influence values and returns the path and distance
     pub fn find_path_influence_large(&self,
-                                     map_type: u8,
+                                     map_type: MapType,
                                      start: (f32, f32),
                                      end: (f32, f32),
                                      possible_heuristic: Option<u8>)
@@ -222,8 +512,8 @@ impl Map {
     }
 
     /// Finds a compromise where low influence matches with close position to the start position.
-    fn find_low_inside_walk(&self,
-                            map_type: u8,
+    pub fn find_low_inside_walk(&self,
+                            map_type: MapType,
                             start: (f32, f32),
                             target: (f32, f32),
                             distance: f32)
@@ -231,9 +521,7 @@ impl Map {
         let map = self.get_map(map_type);
         return map.find_low_inside_walk(start, target, distance);
     }
-}
 
-impl Map {
     pub fn new(pathing: Vec<Vec<usize>>,
                placement: Vec<Vec<usize>>,
                height_map: Vec<Vec<usize>>,
@@ -241,9 +529,29 @@ impl Map {
                y_start: usize,
                x_end: usize,
                y_end: usize)
-               -> Self {
+               -> Result<Self> {
         let width = pathing.len();
-        let height = pathing[0].len();
+        let height = if width > 0 { pathing[0].len() } else { 0 };
+        if width == 0 || height == 0 {
+            return Err(Error::EmptyGrid);
+        }
+        for grid in &[&pathing, &placement, &height_map] {
+            if let Some(column) = grid.iter().find(|column| column.len() != height) {
+                return Err(Error::GridSizeMismatch { expected: (width, height),
+                                                     found: (grid.len(), column.len()) });
+            }
+            if grid.len() != width {
+                return Err(Error::GridSizeMismatch { expected: (width, height),
+                                                     found: (grid.len(), height) });
+            }
+        }
+        // Analysis looks at the neighbours of every cell inside the playable area
+        if x_start == 0 || y_start == 0 || x_start > x_end || y_start > y_end || x_end >= width || y_end >= height {
+            return Err(Error::InvalidPlayableArea { start: (x_start, y_start),
+                                                    end: (x_end, y_end) });
+        }
+
+        let input_hash = input_hash(&pathing, &placement, &height_map, x_start, y_start, x_end, y_end);
         let mut points = vec![vec![map_point::MapPoint::new(); height]; width];
 
         let mut walk_map = vec![vec![0; height]; width];
@@ -262,6 +570,7 @@ impl Map {
                 let pathable = x_start <= x && x <= x_end && y_start <= y && y <= y_end;
                 points[x][y].walkable = walkable;
                 points[x][y].pathable = pathable;
+                points[x][y].placeable = placement[x][y] > 0;
                 points[x][y].height = height_map[x][y];
 
                 if pathable {
@@ -284,8 +593,8 @@ impl Map {
                 if !points[x][y].walkable {
                     let h0 = points[x][y + 1].height;
                     let h1 = points[x][y - 1].height;
-                    if (points[x][y].height >= h0 + DIFFERENCE && h0 > 0)
-                       || (points[x][y].height >= h1 + DIFFERENCE && h1 > 0)
+                    if (points[x][y].height >= h0 + LEVEL_DIFFERENCE && h0 > 0)
+                       || (points[x][y].height >= h1 + LEVEL_DIFFERENCE && h1 > 0)
                     {
                         points[x][y].overlord_spot = true;
                     }
@@ -371,39 +680,53 @@ impl Map {
         }
 
         let air_pathing = PathFind::new_internal(fly_map);
-        let colossus_pathing = PathFind::new_internal(reaper_map.clone());
+        let colossus_pathing = PathFind::new_internal(solve_colossus_map(&points));
         let reaper_pathing = PathFind::new_internal(reaper_map);
 
         let influence_colossus_map = false;
         let influence_reaper_map = false;
         let chokes = group_chokes(&mut choke_lines, &mut points);
-
-        Map { ground_pathing,
-              air_pathing,
-              colossus_pathing,
-              reaper_pathing,
-              points,
-              overlord_spots,
-              influence_colossus_map,
-              influence_reaper_map,
-              chokes }
+        let regions = solve_regions(&mut points, &chokes);
+        let region_graph = RegionGraph::new(&points, &regions, &chokes);
+        let ramps = solve_ramps(&points);
+        let jumps_by_takeoff = solve_reaper_jumps(&points);
+        let placement = original_placement(&points);
+        let creep = vec![vec![0; placement[0].len()]; placement.len()];
+
+        Ok(Map { ground_pathing,
+                 air_pathing,
+                 colossus_pathing,
+                 reaper_pathing,
+                 points,
+                 placement,
+                 creep,
+                 overlord_spots,
+                 influence_colossus_map,
+                 influence_reaper_map,
+                 chokes,
+                 regions,
+                 region_graph,
+                 ramps,
+                 jumps_by_takeoff,
+                 input_hash })
     }
 
-    fn get_map(&self, map_type: u8) -> &PathFind {
-        if map_type == 0 {
-            return &self.ground_pathing;
-        }
-        if map_type == 1 {
-            return &self.reaper_pathing;
-        }
-        if map_type == 2 {
-            return &self.colossus_pathing;
-        }
-        if map_type == 3 {
-            return &self.air_pathing;
+    pub fn get_map(&self, map_type: MapType) -> &PathFind {
+        match map_type {
+            MapType::Ground => &self.ground_pathing,
+            MapType::Reaper => &self.reaper_pathing,
+            MapType::Colossus => &self.colossus_pathing,
+            MapType::Air => &self.air_pathing,
         }
+    }
 
-        panic!("Map type {} does not exist", map_type.to_string());
+    pub fn get_map_mut(&mut self, map_type: MapType) -> &mut PathFind {
+        match map_type {
+            MapType::Ground => &mut self.ground_pathing,
+            MapType::Reaper => &mut self.reaper_pathing,
+            MapType::Colossus => &mut self.colossus_pathing,
+            MapType::Air => &mut self.air_pathing,
+        }
     }
 }
 
@@ -423,7 +746,7 @@ fn flood_fill_overlord(points: &mut Vec<Vec<map_point::MapPoint>>,
 
     if target_height != points[x][y].height {
         // Height difference must be at least 16 below target
-        if target_height < points[x][y].height + DIFFERENCE {
+        if target_height < points[x][y].height + LEVEL_DIFFERENCE {
             return false;
         }
 
@@ -1,5 +1,9 @@
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum Cliff {
@@ -9,13 +13,30 @@ pub enum Cliff {
     Both = 0b0011,
 }
 
-#[pyclass]
-#[derive(Clone)]
+impl Cliff {
+    pub fn from_bits(value: u8) -> Option<Cliff> {
+        match value {
+            0b0000 => Some(Cliff::None),
+            0b0001 => Some(Cliff::Low),
+            0b0010 => Some(Cliff::High),
+            0b0011 => Some(Cliff::Both),
+            _ => None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
 pub struct MapPoint {
-    pub zone_index: i8,
+    /// Index in `Map.regions`, -1 on cells that are not walkable
+    pub zone_index: i32,
     pub cliff_type: Cliff,
     pub pathable: bool,
     pub walkable: bool,
+    /// Buildings can be placed on the cell
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub placeable: bool,
     pub climbable: bool,
     pub structure_index: i32,
     pub height: usize,
@@ -26,10 +47,11 @@ pub struct MapPoint {
 
 impl MapPoint {
     pub fn new() -> Self {
-        let zone_index = 0_i8;
+        let zone_index = -1_i32;
         let cliff_type = Cliff::None;
         let pathable = false;
         let walkable = false;
+        let placeable = false;
         let climbable = false;
         let structure_index = 0_i32;
         let height = 0;
@@ -41,6 +63,7 @@ impl MapPoint {
                    cliff_type,
                    pathable,
                    walkable,
+                   placeable,
                    climbable,
                    structure_index,
                    height,
@@ -0,0 +1,53 @@
+#[cfg(feature = "python")]
+use pyo3::exceptions::ValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Single pathing layer of a `Map`, same values as `sc2pathlibp.mappings.MapType`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MapType {
+    Ground = 0,
+    Reaper = 1,
+    Colossus = 2,
+    Air = 3,
+}
+
+impl MapType {
+    pub const ALL: [MapType; 4] = [MapType::Ground, MapType::Reaper, MapType::Colossus, MapType::Air];
+
+    pub fn from_u8(value: u8) -> Option<MapType> {
+        match value {
+            0 => Some(MapType::Ground),
+            1 => Some(MapType::Reaper),
+            2 => Some(MapType::Colossus),
+            3 => Some(MapType::Air),
+            _ => None,
+        }
+    }
+}
+
+/// Group of layers influence is added to, same values as `sc2pathlibp.mappings.MapsType`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MapsType {
+    PureGround = 0,
+    Ground = 1,
+    Air = 2,
+    Both = 3,
+}
+
+impl MapsType {
+    pub fn from_usize(value: usize) -> Option<MapsType> {
+        match value {
+            0 => Some(MapsType::PureGround),
+            1 => Some(MapsType::Ground),
+            2 => Some(MapsType::Air),
+            3 => Some(MapsType::Both),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+pub(crate) fn map_type_py(value: u8) -> PyResult<MapType> {
+    MapType::from_u8(value).ok_or_else(|| ValueError::py_err(format!("Map type {} does not exist", value)))
+}
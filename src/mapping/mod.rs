@@ -1,5 +1,32 @@
+#[cfg(feature = "json")]
+pub mod analysis;
+pub mod cache;
 mod chokes;
 mod climb;
+mod colossus;
+pub mod creep;
+pub mod expansions;
 pub mod influence;
 pub mod map;
 pub mod map_point;
+pub mod mappings;
+pub mod placement;
+pub mod ramps;
+pub mod reaper;
+pub mod region_graph;
+pub mod regions;
+pub mod terrain;
+pub mod tumors;
+pub mod walls;
+pub mod warp_in;
+
+#[cfg(feature = "json")]
+pub use analysis::MapAnalysis;
+pub use chokes::Choke;
+pub use expansions::Expansion;
+pub use mappings::{MapType, MapsType};
+pub use placement::PlacementOptions;
+pub use ramps::Ramp;
+pub use reaper::ReaperJump;
+pub use region_graph::{RegionConnection, RegionGraph, RegionRoute};
+pub use regions::Region;
@@ -0,0 +1,249 @@
+use crate::mapping::expansions::Expansion;
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+use crate::path_find::rectangle::Rectangle;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+
+/// Workers walk within this distance of the line between a town hall and its resources.
+const MINERAL_LINE_WIDTH: f32 = 1.5;
+
+/// Constraints for the placement searches.
+#[derive(Clone, Debug, Default)]
+pub struct PlacementOptions {
+    /// Keep the cells around the building pathable so that units can walk past it
+    pub walkway: bool,
+    /// Keep the area between these town halls and their resources free for workers
+    pub mineral_lines: Vec<Expansion>,
+    /// Only place on creep, as Zerg buildings require
+    pub creep: bool,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Whether a building of the size fits on placeable cells at the position.
+    #[name = "can_place"]
+    fn can_place_py(&self, size: (usize, usize), center: (f32, f32)) -> bool { self.can_place(size, center) }
+
+    /// Closest position to the center where a building of the size can be placed.
+    #[name = "find_placement_near"]
+    #[args(walkway = "false", mineral_lines = "None", creep = "false")]
+    #[allow(clippy::too_many_arguments)]
+    fn find_placement_near_py(&self,
+                              py: Python,
+                              size: (usize, usize),
+                              center: (f32, f32),
+                              max_distance: f32,
+                              walkway: bool,
+                              mineral_lines: Option<Vec<Expansion>>,
+                              creep: bool)
+                              -> Option<(f32, f32)> {
+        let options = PlacementOptions { walkway,
+                                         mineral_lines: mineral_lines.unwrap_or_default(),
+                                         creep };
+        py.allow_threads(|| self.find_placement_near(size, center, max_distance, &options))
+    }
+
+    /// Up to `count` positions for buildings of the size that don't overlap each other,
+    /// lowest ground influence first and then closest to the center.
+    #[name = "find_placements"]
+    #[args(walkway = "false", mineral_lines = "None", creep = "false")]
+    #[allow(clippy::too_many_arguments)]
+    fn find_placements_py(&self,
+                          py: Python,
+                          size: (usize, usize),
+                          center: (f32, f32),
+                          max_distance: f32,
+                          count: usize,
+                          walkway: bool,
+                          mineral_lines: Option<Vec<Expansion>>,
+                          creep: bool)
+                          -> Vec<(f32, f32)> {
+        let options = PlacementOptions { walkway,
+                                         mineral_lines: mineral_lines.unwrap_or_default(),
+                                         creep };
+        py.allow_threads(|| self.find_placements(size, center, max_distance, count, &options))
+    }
+}
+
+impl Map {
+    /// Whether a building of the size fits on placeable cells at the position.
+    pub fn can_place(&self, size: (usize, usize), center: (f32, f32)) -> bool {
+        if center.0 < 0.0 || center.1 < 0.0 {
+            return false;
+        }
+
+        let rect = Rectangle::init_from_center(center, size, self.ground_pathing.width, self.ground_pathing.height);
+        rect.x_end - rect.x == size.0 && rect.y_end - rect.y == size.1 && self.is_free(&rect)
+    }
+
+    /// Closest position to the center within `max_distance` where a building of the size can be placed.
+    ///
+    /// Positions are searched in growing distance from the center, aligned to the grid like the game places buildings.
+    pub fn find_placement_near(&self,
+                               size: (usize, usize),
+                               center: (f32, f32),
+                               max_distance: f32,
+                               options: &PlacementOptions)
+                               -> Option<(f32, f32)> {
+        self.placement_candidates(size, center, max_distance, options)
+            .into_iter()
+            .next()
+            .map(|(position, _, _)| position)
+    }
+
+    /// Up to `count` positions within `max_distance` for buildings of the size that don't overlap each other.
+    ///
+    /// Positions with the lowest ground influence on the footprint are picked first, ties go to the closest position.
+    pub fn find_placements(&self,
+                           size: (usize, usize),
+                           center: (f32, f32),
+                           max_distance: f32,
+                           count: usize,
+                           options: &PlacementOptions)
+                           -> Vec<(f32, f32)> {
+        let mut candidates = self.placement_candidates(size, center, max_distance, options);
+        let normal_influence = self.ground_pathing.normal_influence();
+        let influence = |rect: &Rectangle| -> usize {
+            self.ground_pathing.map[rect.x..rect.x_end]
+                .iter()
+                .flat_map(|column| column[rect.y..rect.y_end].iter())
+                .map(|value| value.saturating_sub(normal_influence))
+                .sum()
+        };
+        // Stable sort keeps the distance order between positions with the same influence
+        candidates.sort_by_key(|(_, rect, _)| influence(rect));
+
+        let mut picked: Vec<((f32, f32), Rectangle)> = Vec::new();
+        for (position, rect, _) in candidates {
+            if picked.len() >= count {
+                break;
+            }
+            if picked.iter().all(|(_, other)| rect.gap(other) >= 0) {
+                picked.push((position, rect));
+            }
+        }
+
+        picked.into_iter().map(|(position, _)| position).collect()
+    }
+
+    /// Valid building positions with their footprint and distance, closest first.
+    fn placement_candidates(&self,
+                            size: (usize, usize),
+                            center: (f32, f32),
+                            max_distance: f32,
+                            options: &PlacementOptions)
+                            -> Vec<((f32, f32), Rectangle, f32)> {
+        let width = self.ground_pathing.width;
+        let height = self.ground_pathing.height;
+        let half = (size.0 as f32 / 2.0, size.1 as f32 / 2.0);
+        let reach = max_distance.max(0.0).ceil() as isize + 1;
+        let mut candidates = Vec::new();
+
+        for dx in -reach..=reach {
+            for dy in -reach..=reach {
+                // Lowest corner of the footprint
+                let x = (center.0 - half.0).round() as isize + dx;
+                let y = (center.1 - half.1).round() as isize + dy;
+                if x < 0 || y < 0 || x as usize + size.0 > width || y as usize + size.1 > height {
+                    continue;
+                }
+
+                let position = (x as f32 + half.0, y as f32 + half.1);
+                let distance = ((position.0 - center.0).powi(2) + (position.1 - center.1).powi(2)).sqrt();
+                let rect = Rectangle { x: x as usize,
+                                       y: y as usize,
+                                       x_end: x as usize + size.0,
+                                       y_end: y as usize + size.1 };
+                if distance <= max_distance && self.is_free(&rect) && self.meets_options(&rect, options) {
+                    candidates.push((position, rect, distance));
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+        candidates
+    }
+
+    fn is_free(&self, rect: &Rectangle) -> bool {
+        self.placement[rect.x..rect.x_end].iter().all(|column| column[rect.y..rect.y_end].iter().all(|cell| *cell > 0))
+    }
+
+    fn meets_options(&self, rect: &Rectangle, options: &PlacementOptions) -> bool {
+        if options.creep && !self.is_on_creep(rect) {
+            return false;
+        }
+
+        if options.walkway {
+            let x_end = (rect.x_end + 1).min(self.ground_pathing.width);
+            let y_end = (rect.y_end + 1).min(self.ground_pathing.height);
+            for x in rect.x.saturating_sub(1)..x_end {
+                for y in rect.y.saturating_sub(1)..y_end {
+                    let inside = rect.x <= x && x < rect.x_end && rect.y <= y && y < rect.y_end;
+                    if !inside && self.ground_pathing.map[x][y] == 0 {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        for expansion in &options.mineral_lines {
+            for resource in expansion.minerals.iter().chain(&expansion.geysers) {
+                for x in rect.x..rect.x_end {
+                    for y in rect.y..rect.y_end {
+                        let cell = (x as f32 + 0.5, y as f32 + 0.5);
+                        if segment_distance(cell, expansion.center, *resource) < MINERAL_LINE_WIDTH {
+                            return false;
+                        }
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Marks the footprint as placeable or not, cells that were never placeable or are under an obstacle stay
+    /// unplaceable.
+    pub(crate) fn set_placement(&mut self, center: (f32, f32), size: (usize, usize), placeable: bool) {
+        let rect = Rectangle::init_from_center(center, size, self.ground_pathing.width, self.ground_pathing.height);
+        for x in rect.x..rect.x_end {
+            for y in rect.y..rect.y_end {
+                if placeable {
+                    self.restore_placement(x, y);
+                } else {
+                    self.placement[x][y] = 0;
+                }
+            }
+        }
+    }
+
+    /// Gives the cell its placement back from the map points, unless an obstacle still covers it.
+    pub(crate) fn restore_placement(&mut self, x: usize, y: usize) {
+        if !self.ground_pathing.is_placement_obstacle((x, y)) {
+            self.placement[x][y] = self.points[x][y].placeable as usize;
+        }
+    }
+}
+
+/// Placement layer as the map was created.
+pub(crate) fn original_placement(points: &[Vec<MapPoint>]) -> Vec<Vec<usize>> {
+    points.iter().map(|column| column.iter().map(|point| point.placeable as usize).collect()).collect()
+}
+
+fn segment_distance(point: (f32, f32), start: (f32, f32), end: (f32, f32)) -> f32 {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let (px, py) = (point.0 - start.0, point.1 - start.1);
+    let dot = px * dx + py * dy;
+    let length = dx * dx + dy * dy;
+
+    if dot <= 0.0 || length == 0.0 {
+        px.hypot(py)
+    } else if dot >= length {
+        (point.0 - end.0).hypot(point.1 - end.1)
+    } else {
+        (px * dy - py * dx).abs() / length.sqrt()
+    }
+}
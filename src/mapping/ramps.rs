@@ -0,0 +1,230 @@
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Unbuildable areas with less height difference between their sides are flat patches rather than ramps.
+const MIN_RAMP_CLIMB: usize = 8;
+
+/// Slope between two height levels, detected as walkable cells that can't be built on with ground on both levels
+/// next to them.
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Ramp {
+    /// Index in `Map.ramps`
+    pub index: usize,
+    pub cells: Vec<(usize, usize)>,
+    /// Height of the ground at the top of the ramp
+    pub upper_height: usize,
+    /// Height of the ground at the bottom of the ramp
+    pub lower_height: usize,
+    /// Ramp cells next to the upper ground
+    pub upper: Vec<(usize, usize)>,
+    /// Ramp cells next to the lower ground
+    pub lower: Vec<(usize, usize)>,
+    /// Middle of the upper cells, in cell coordinates like `Region.centroid`
+    pub top_center: (f32, f32),
+    /// Middle of the lower cells
+    pub bottom_center: (f32, f32),
+    /// Unit vector pointing down the ramp
+    pub direction: (f32, f32),
+    /// Extent of the ramp across `direction`
+    pub width: f32,
+    /// Region of the ground at the top of the ramp
+    pub upper_region: Option<usize>,
+    /// Region of the ground at the bottom of the ramp
+    pub lower_region: Option<usize>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Ramp {
+    #[getter(index)]
+    fn get_index(&self) -> usize { self.index }
+
+    #[getter(cells)]
+    fn get_cells(&self) -> Vec<(usize, usize)> { self.cells.clone() }
+
+    #[getter(upper_height)]
+    fn get_upper_height(&self) -> usize { self.upper_height }
+
+    #[getter(lower_height)]
+    fn get_lower_height(&self) -> usize { self.lower_height }
+
+    #[getter(upper)]
+    fn get_upper(&self) -> Vec<(usize, usize)> { self.upper.clone() }
+
+    #[getter(lower)]
+    fn get_lower(&self) -> Vec<(usize, usize)> { self.lower.clone() }
+
+    #[getter(top_center)]
+    fn get_top_center(&self) -> (f32, f32) { self.top_center }
+
+    #[getter(bottom_center)]
+    fn get_bottom_center(&self) -> (f32, f32) { self.bottom_center }
+
+    #[getter(direction)]
+    fn get_direction(&self) -> (f32, f32) { self.direction }
+
+    #[getter(width)]
+    fn get_width(&self) -> f32 { self.width }
+
+    #[getter(upper_region)]
+    fn get_upper_region(&self) -> Option<usize> { self.upper_region }
+
+    #[getter(lower_region)]
+    fn get_lower_region(&self) -> Option<usize> { self.lower_region }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    #[getter(ramps)]
+    fn get_ramps(&self) -> Vec<Ramp> { self.ramps.clone() }
+
+    /// Ramp leading out of the main base of the start location.
+    #[name = "main_ramp"]
+    fn main_ramp_py(&self, start_location: (f32, f32)) -> Option<Ramp> { self.main_ramp(start_location).cloned() }
+}
+
+impl Map {
+    /// Ramp leading down from the region of the start location that is closest to it.
+    pub fn main_ramp(&self, start_location: (f32, f32)) -> Option<&Ramp> {
+        let region = self.zone_at(start_location)?;
+        let distance = |ramp: &&Ramp| {
+            (ramp.top_center.0 - start_location.0).powi(2) + (ramp.top_center.1 - start_location.1).powi(2)
+        };
+
+        self.ramps
+            .iter()
+            .filter(|ramp| ramp.upper_region == Some(region))
+            .min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap_or(Ordering::Equal))
+    }
+}
+
+/// Finds the ramps of the map, `zone_index` of the map points must be set.
+pub fn solve_ramps(points: &[Vec<MapPoint>]) -> Vec<Ramp> {
+    let width = points.len();
+    let height = points[0].len();
+    let mut visited = vec![vec![false; height]; width];
+    let mut ramps = Vec::new();
+
+    for x in 0..width {
+        for y in 0..height {
+            if visited[x][y] || !is_ramp_cell(&points[x][y]) {
+                continue;
+            }
+
+            let mut cells = Vec::new();
+            let mut stack = vec![(x, y)];
+            visited[x][y] = true;
+            while let Some(pos) = stack.pop() {
+                cells.push(pos);
+                for (nx, ny) in neighbours(pos, width, height) {
+                    if !visited[nx][ny] && is_ramp_cell(&points[nx][ny]) {
+                        visited[nx][ny] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+
+            if let Some(ramp) = create_ramp(points, cells, ramps.len()) {
+                ramps.push(ramp);
+            }
+        }
+    }
+
+    ramps
+}
+
+fn create_ramp(points: &[Vec<MapPoint>], mut cells: Vec<(usize, usize)>, index: usize) -> Option<Ramp> {
+    let width = points.len();
+    let height = points[0].len();
+    // Buildable ground around the ramp
+    let ground = |pos: (usize, usize)| {
+        neighbours(pos, width, height).filter(|&(x, y)| points[x][y].walkable && points[x][y].placeable)
+    };
+
+    let heights: Vec<usize> = cells.iter().flat_map(|&pos| ground(pos)).map(|(x, y)| points[x][y].height).collect();
+    let upper_height = *heights.iter().max()?;
+    let lower_height = *heights.iter().min()?;
+    if upper_height - lower_height < MIN_RAMP_CLIMB {
+        return None;
+    }
+
+    cells.sort();
+    let is_upper = |height: usize| upper_height - height < height - lower_height;
+    let mut upper = Vec::new();
+    let mut lower = Vec::new();
+    let mut upper_zones = HashMap::new();
+    let mut lower_zones = HashMap::new();
+
+    for &pos in &cells {
+        let (mut touches_upper, mut touches_lower) = (false, false);
+        for (x, y) in ground(pos) {
+            let (touches, zones) = if is_upper(points[x][y].height) {
+                (&mut touches_upper, &mut upper_zones)
+            } else {
+                (&mut touches_lower, &mut lower_zones)
+            };
+            *touches = true;
+            if points[x][y].zone_index >= 0 {
+                *zones.entry(points[x][y].zone_index as usize).or_insert(0) += 1;
+            }
+        }
+
+        if touches_upper {
+            upper.push(pos);
+        }
+        if touches_lower {
+            lower.push(pos);
+        }
+    }
+
+    let top_center = center(&upper);
+    let bottom_center = center(&lower);
+    let (dx, dy) = (bottom_center.0 - top_center.0, bottom_center.1 - top_center.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    let direction = if length > 0.0 { (dx / length, dy / length) } else { (0.0, 0.0) };
+
+    let across = cells.iter().map(|&(x, y)| y as f32 * direction.0 - x as f32 * direction.1);
+    let width = across.clone().fold(f32::MIN, f32::max) - across.fold(f32::MAX, f32::min) + 1.0;
+
+    Some(Ramp { index,
+                cells,
+                upper_height,
+                lower_height,
+                upper,
+                lower,
+                top_center,
+                bottom_center,
+                direction,
+                width,
+                upper_region: most_common(&upper_zones),
+                lower_region: most_common(&lower_zones) })
+}
+
+#[inline]
+fn is_ramp_cell(point: &MapPoint) -> bool { point.walkable && point.pathable && !point.placeable }
+
+fn neighbours(pos: (usize, usize), width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    let x_range = pos.0.saturating_sub(1)..(pos.0 + 2).min(width);
+    x_range.flat_map(move |x| (pos.1.saturating_sub(1)..(pos.1 + 2).min(height)).map(move |y| (x, y)))
+           .filter(move |&neighbour| neighbour != pos)
+}
+
+fn center(cells: &[(usize, usize)]) -> (f32, f32) {
+    let count = cells.len().max(1) as f32;
+    (cells.iter().map(|cell| cell.0 as f32).sum::<f32>() / count,
+     cells.iter().map(|cell| cell.1 as f32).sum::<f32>() / count)
+}
+
+/// Ties are broken by the lower index to keep the result stable
+fn most_common(counts: &HashMap<usize, usize>) -> Option<usize> {
+    counts.iter().max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0))).map(|(zone, _)| *zone)
+}
@@ -0,0 +1,233 @@
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+use crate::mapping::terrain::height_to_level;
+use crate::path_find::pos::{InfluencedPos, MULTF32, SQRT2};
+use pathfinding::prelude::{astar, dijkstra_all};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// Widest strip of climbable cliff cells a reaper jumps over.
+const MAX_JUMP_CELLS: usize = 3;
+/// Time of a jump in walking distance, used when no other cost is given.
+pub const DEFAULT_JUMP_COST: f32 = 2.0;
+
+const DIRECTIONS: [(isize, isize); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Reaper jump over a cliff from the walkable cell `takeoff` to the walkable cell `landing`.
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReaperJump {
+    pub takeoff: (usize, usize),
+    pub landing: (usize, usize),
+    /// Whether the jump goes up to a higher terrain level
+    pub up: bool,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl ReaperJump {
+    #[getter(takeoff)]
+    fn get_takeoff(&self) -> (usize, usize) { self.takeoff }
+
+    #[getter(landing)]
+    fn get_landing(&self) -> (usize, usize) { self.landing }
+
+    #[getter(up)]
+    fn get_up(&self) -> bool { self.up }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Every jump a reaper can make, in both directions.
+    #[getter(reaper_jumps)]
+    fn get_reaper_jumps(&self) -> Vec<ReaperJump> { self.reaper_jumps() }
+
+    /// Path for a reaper where each jump takes `jump_cost` in walking distance, returns the path, its distance
+    /// and the jumps along it.
+    #[name = "find_path_reaper"]
+    #[args(jump_cost = "2.0")]
+    fn find_path_reaper_py(&self,
+                           py: Python,
+                           start: (f32, f32),
+                           end: (f32, f32),
+                           jump_cost: f32)
+                           -> (Vec<(usize, usize)>, f32, Vec<ReaperJump>) {
+        py.allow_threads(|| self.find_path_reaper(start, end, jump_cost))
+    }
+
+    /// Closest jump by influenced walking distance that lands on a cell with at most `max_influence` reaper
+    /// influence above normal, with the influenced distance to it.
+    #[name = "reaper_escape_jump"]
+    #[args(max_influence = "0")]
+    fn reaper_escape_jump_py(&self,
+                             py: Python,
+                             start: (f32, f32),
+                             max_influence: usize)
+                             -> Option<(ReaperJump, f32)> {
+        py.allow_threads(|| self.reaper_escape_jump(start, max_influence))
+    }
+}
+
+/// Finds the jumps over a strip of at most `MAX_JUMP_CELLS` climbable cells between two terrain levels, in both
+/// directions, by takeoff cell.
+///
+/// The cells jumped over are never walkable, so blocks only matter on the takeoff and landing and are checked
+/// when the jumps are used.
+pub fn solve_reaper_jumps(points: &[Vec<MapPoint>]) -> HashMap<(usize, usize), Vec<ReaperJump>> {
+    let width = points.len();
+    let height = points[0].len();
+    let mut jumps: HashMap<(usize, usize), Vec<ReaperJump>> = HashMap::new();
+
+    for x in 0..width {
+        for y in 0..height {
+            if !points[x][y].walkable {
+                continue;
+            }
+
+            for direction in &DIRECTIONS {
+                if let Some(landing) = jump_landing(points, (x, y), *direction) {
+                    let level = height_to_level(points[x][y].height);
+                    let landing_level = height_to_level(points[landing.0][landing.1].height);
+                    if landing_level != level {
+                        let jump = ReaperJump { takeoff: (x, y), landing, up: landing_level > level };
+                        jumps.entry((x, y)).or_default().push(jump);
+                    }
+                }
+            }
+        }
+    }
+
+    jumps
+}
+
+/// Walkable cell on the other side of the climbable cells in the direction.
+fn jump_landing(points: &[Vec<MapPoint>],
+                takeoff: (usize, usize),
+                direction: (isize, isize))
+                -> Option<(usize, usize)> {
+    let (mut x, mut y) = (takeoff.0 as isize, takeoff.1 as isize);
+
+    for steps in 1..=MAX_JUMP_CELLS + 1 {
+        x += direction.0;
+        y += direction.1;
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        let point = points.get(x as usize)?.get(y as usize)?;
+        if point.walkable {
+            return if steps > 1 { Some((x as usize, y as usize)) } else { None };
+        }
+        if !point.climbable {
+            return None;
+        }
+    }
+
+    None
+}
+
+impl Map {
+    /// Every jump a reaper can make over a strip of at most `MAX_JUMP_CELLS` climbable cells between two terrain
+    /// levels, in both directions. Cells blocked on the reaper pathing can't be jumped from or to.
+    pub fn reaper_jumps(&self) -> Vec<ReaperJump> {
+        let mut takeoffs: Vec<&(usize, usize)> = self.jumps_by_takeoff.keys().collect();
+        takeoffs.sort();
+
+        takeoffs.into_iter().flat_map(|takeoff| self.jumps_from(*takeoff)).cloned().collect()
+    }
+
+    /// Path on the reaper pathing with influence where climbable cliffs are crossed only by jumps.
+    ///
+    /// A jump costs `jump_cost` walking distance on the landing cell, so jumps into influence cost more.
+    /// Returns the path, its distance and the jumps along it, an empty path when the end can't be reached.
+    pub fn find_path_reaper(&self,
+                            start: (f32, f32),
+                            end: (f32, f32),
+                            jump_cost: f32)
+                            -> (Vec<(usize, usize)>, f32, Vec<ReaperJump>) {
+        let pathing = &self.reaper_pathing;
+        let start = pathing.get_closest_pathable((start.0.round() as usize, start.1.round() as usize));
+        let end = pathing.get_closest_pathable((end.0.round() as usize, end.1.round() as usize));
+        let goal = InfluencedPos(end.0, end.1);
+        // A jump can be cheaper than walking the cells it skips, so the octile distance is scaled down to the
+        // cheapest cost per cell of a walk or the longest jump to stay admissible
+        let longest_jump = ((MAX_JUMP_CELLS + 1) * SQRT2) as f32;
+        let scale = if jump_cost > 0.0 { (jump_cost * MULTF32 / longest_jump).min(1.0) } else { 0.0 };
+        let normal_influence = pathing.normal_influence();
+
+        let result = astar(&InfluencedPos(start.0, start.1),
+                           |p| self.reaper_successors(p, true, jump_cost),
+                           |p| (p.octile_distance(&goal, normal_influence) as f32 * scale) as usize,
+                           |p| *p == goal);
+
+        match result {
+            None => (Vec::new(), 0.0, Vec::new()),
+            Some((positions, cost)) => {
+                let path: Vec<(usize, usize)> = positions.into_iter().map(|pos| (pos.0, pos.1)).collect();
+                let taken = path.windows(2)
+                                .filter_map(|step| {
+                                    self.jumps_from(step[0]).find(|jump| jump.landing == step[1]).cloned()
+                                })
+                                .collect();
+                (path, cost as f32 / MULTF32, taken)
+            }
+        }
+    }
+
+    /// Closest jump from the start by walking distance weighted with the reaper influence, like the distance of
+    /// `find_path_reaper`, that lands on a cell with at most `max_influence` reaper influence above normal. Returns
+    /// the jump with the weighted distance to its takeoff.
+    pub fn reaper_escape_jump(&self, start: (f32, f32), max_influence: usize) -> Option<(ReaperJump, f32)> {
+        let pathing = &self.reaper_pathing;
+        let start = pathing.get_closest_pathable((start.0.round() as usize, start.1.round() as usize));
+        let normal_influence = pathing.normal_influence();
+        let mut reached: Vec<((usize, usize), usize)> =
+            dijkstra_all(&InfluencedPos(start.0, start.1), |p| self.reaper_successors(p, false, 0.0))
+                .into_iter()
+                .map(|(pos, (_, cost))| ((pos.0, pos.1), cost))
+                .chain(std::iter::once((start, 0)))
+                .collect();
+        reached.sort();
+        reached.sort_by_key(|(_, cost)| *cost);
+
+        for (pos, cost) in reached {
+            let escape = self.jumps_from(pos).find(|jump| {
+                                                 let value = pathing.map[jump.landing.0][jump.landing.1];
+                                                 value.saturating_sub(normal_influence) <= max_influence
+                                             });
+            if let Some(jump) = escape {
+                return Some((jump.clone(), cost as f32 / MULTF32));
+            }
+        }
+
+        None
+    }
+
+    /// Jumps from the takeoff cell that aren't blocked on the reaper pathing.
+    fn jumps_from(&self, takeoff: (usize, usize)) -> impl Iterator<Item = &ReaperJump> {
+        let grid = &self.reaper_pathing.map;
+        self.jumps_by_takeoff
+            .get(&takeoff)
+            .into_iter()
+            .flatten()
+            .filter(move |jump| grid[jump.takeoff.0][jump.takeoff.1] > 0 && grid[jump.landing.0][jump.landing.1] > 0)
+    }
+
+    /// Walking steps to walkable cells and, with `jumps`, jumps from the position.
+    fn reaper_successors(&self, pos: &InfluencedPos, jumps: bool, jump_cost: f32) -> Vec<(InfluencedPos, usize)> {
+        let grid = &self.reaper_pathing.map;
+        let mut successors: Vec<(InfluencedPos, usize)> =
+            pos.successors(grid).into_iter().filter(|(next, _)| self.points[next.0][next.1].walkable).collect();
+
+        if jumps {
+            for jump in self.jumps_from((pos.0, pos.1)) {
+                let cost = (jump_cost * MULTF32) as usize * grid[jump.landing.0][jump.landing.1];
+                successors.push((InfluencedPos(jump.landing.0, jump.landing.1), cost));
+            }
+        }
+
+        successors
+    }
+}
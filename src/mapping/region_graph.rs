@@ -0,0 +1,431 @@
+use crate::binary::{invalid_data, BinaryReader, BinaryWriter};
+use crate::mapping::chokes::Choke;
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+use crate::mapping::regions::Region;
+use crate::path_find::pos::{Pos, MULTF32};
+use pathfinding::prelude::{dijkstra, dijkstra_all};
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+use std::f32;
+use std::io;
+
+/// Choke that connects two regions.
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionConnection {
+    /// Index in `Map.chokes`
+    pub choke: usize,
+    pub regions: (usize, usize),
+}
+
+/// Regions and chokes passed from one region to another, distance is measured from the start region center
+/// through the choke centers to the end region center.
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionRoute {
+    pub regions: Vec<usize>,
+    pub chokes: Vec<usize>,
+    pub distance: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Edge {
+    to: usize,
+    cost: usize,
+    /// Region the edge runs through
+    region: usize,
+}
+
+/// Graph with regions as nodes connected through their chokes.
+///
+/// Internally both region centers and choke centers are nodes, nodes `0..regions` are regions and the rest are chokes.
+/// Edges are ground distances inside a single region, so going through a region costs the walk from the choke
+/// where the region was entered to the choke where it is left.
+#[derive(Clone, Debug)]
+pub struct RegionGraph {
+    /// Walkable cell of each region closest to its centroid
+    pub centers: Vec<(usize, usize)>,
+    /// Walkable cell in the middle of each choke, `None` for chokes without pixels which are left out of the graph
+    pub choke_centers: Vec<Option<(usize, usize)>>,
+    pub connections: Vec<RegionConnection>,
+    /// Ground distance between the region centers, infinite when one can't be reached from the other.
+    ///
+    /// Measured along the graph, from the center of the start region through the choke centers on the way to the
+    /// center of the end region, so it is a bit longer than the shortest walk between the centers.
+    pub distances: Vec<Vec<f32>>,
+    edges: Vec<Vec<Edge>>,
+}
+
+impl RegionGraph {
+    pub fn new(points: &[Vec<MapPoint>], regions: &[Region], chokes: &[Choke]) -> RegionGraph {
+        let region_count = regions.len();
+        let centers: Vec<(usize, usize)> = regions.iter().map(|region| region_center(points, region)).collect();
+        let choke_centers: Vec<Option<(usize, usize)>> = chokes.iter().map(choke_center).collect();
+        let mut edges = vec![Vec::new(); region_count + chokes.len()];
+        let mut connections = Vec::new();
+
+        for choke_index in 0..chokes.len() {
+            let bordering: Vec<usize> = regions.iter()
+                                               .filter(|region| region.chokes.contains(&choke_index))
+                                               .map(|region| region.index)
+                                               .collect();
+            for (i, &first) in bordering.iter().enumerate() {
+                for &second in &bordering[i + 1..] {
+                    connections.push(RegionConnection { choke: choke_index,
+                                                        regions: (first, second) });
+                }
+            }
+        }
+
+        for region in regions {
+            let (grid, offset) = region_grid(points, region, chokes);
+            let local = |pos: (usize, usize)| (pos.0 - offset.0, pos.1 - offset.1);
+
+            let center_node = region.index;
+            for (i, &choke_index) in region.chokes.iter().enumerate() {
+                let choke_center = match choke_centers[choke_index] {
+                    Some(center) => center,
+                    None => continue,
+                };
+                let costs = walk_costs(&grid, local(choke_center));
+                let cost_to = |target: (usize, usize)| {
+                    let (x, y) = local(target);
+                    Some(costs[x][y]).filter(|c| *c < usize::MAX)
+                };
+
+                let choke_node = region_count + choke_index;
+                if let Some(cost) = cost_to(centers[region.index]) {
+                    add_edge(&mut edges, center_node, choke_node, cost, region.index);
+                }
+
+                for &other in &region.chokes[i + 1..] {
+                    if let Some(cost) = choke_centers[other].and_then(cost_to) {
+                        add_edge(&mut edges, choke_node, region_count + other, cost, region.index);
+                    }
+                }
+            }
+        }
+
+        let distances = center_distances(&edges, region_count);
+
+        RegionGraph { centers,
+                      choke_centers,
+                      connections,
+                      distances,
+                      edges }
+    }
+
+    pub(crate) fn write_binary(&self, writer: &mut BinaryWriter) {
+        writer.write_points(&self.centers);
+        writer.write_usize(self.choke_centers.len());
+        for center in &self.choke_centers {
+            writer.write_bool(center.is_some());
+            writer.write_point(center.unwrap_or((0, 0)));
+        }
+
+        writer.write_usize(self.connections.len());
+        for connection in &self.connections {
+            writer.write_usize(connection.choke);
+            writer.write_point(connection.regions);
+        }
+
+        for node in &self.edges {
+            writer.write_usize(node.len());
+            for edge in node {
+                writer.write_usize(edge.to);
+                writer.write_usize(edge.cost);
+                writer.write_usize(edge.region);
+            }
+        }
+    }
+
+    /// Reads a graph written by `write_binary` for the given number of regions and chokes.
+    pub(crate) fn read_binary(reader: &mut BinaryReader,
+                              region_count: usize,
+                              choke_count: usize)
+                              -> io::Result<RegionGraph> {
+        let centers = reader.read_points()?;
+        let count = reader.read_len(3)?;
+        let mut choke_centers = Vec::with_capacity(count);
+        for _ in 0..count {
+            let has_center = reader.read_bool()?;
+            let center = reader.read_point()?;
+            choke_centers.push(if has_center { Some(center) } else { None });
+        }
+        if centers.len() != region_count || choke_centers.len() != choke_count {
+            return Err(invalid_data("region graph does not match regions"));
+        }
+
+        let count = reader.read_len(3)?;
+        let mut connections = Vec::with_capacity(count);
+        for _ in 0..count {
+            let choke = reader.read_usize()?;
+            let regions = reader.read_point()?;
+            if choke >= choke_count || regions.0 >= region_count || regions.1 >= region_count {
+                return Err(invalid_data("region connection out of range"));
+            }
+            connections.push(RegionConnection { choke, regions });
+        }
+
+        let node_count = region_count + choke_count;
+        let mut edges = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let count = reader.read_len(3)?;
+            let mut node = Vec::with_capacity(count);
+            for _ in 0..count {
+                let edge = Edge { to: reader.read_usize()?,
+                                  cost: reader.read_usize()?,
+                                  region: reader.read_usize()? };
+                if edge.to >= node_count || edge.region >= region_count {
+                    return Err(invalid_data("region graph edge out of range"));
+                }
+                node.push(edge);
+            }
+            edges.push(node);
+        }
+
+        let distances = center_distances(&edges, region_count);
+        Ok(RegionGraph { centers,
+                         choke_centers,
+                         connections,
+                         distances,
+                         edges })
+    }
+
+    /// Ground distance between the centers of every two chokes that border the same region, as
+    /// `(first, second, distance)` with `first < second`, walking inside the shared region.
+    pub fn choke_edges(&self) -> Vec<(usize, usize, f32)> {
+        let region_count = self.centers.len();
+        let mut shortest: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+
+        for (node, edges) in self.edges.iter().enumerate().skip(region_count) {
+            for edge in edges.iter().filter(|edge| edge.to > node) {
+                let key = (node - region_count, edge.to - region_count);
+                let cost = shortest.entry(key).or_insert(edge.cost);
+                *cost = (*cost).min(edge.cost);
+            }
+        }
+
+        shortest.into_iter().map(|((first, second), cost)| (first, second, cost as f32 / MULTF32)).collect()
+    }
+
+    /// Regions next to the region with the chokes connecting them.
+    pub fn neighbours(&self, region: usize) -> Vec<(usize, usize)> {
+        self.connections
+            .iter()
+            .filter_map(|connection| match connection.regions {
+                (first, second) if first == region => Some((second, connection.choke)),
+                (first, second) if second == region => Some((first, connection.choke)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Shortest route between the centers of two regions.
+    pub fn route(&self, start: usize, end: usize) -> Option<RegionRoute> {
+        let region_count = self.centers.len();
+        if start >= region_count || end >= region_count {
+            return None;
+        }
+
+        let (nodes, cost) = dijkstra(&start, |node| successors(&self.edges, *node), |node| *node == end)?;
+        let mut regions = vec![start];
+        let mut chokes = Vec::new();
+
+        for pair in nodes.windows(2) {
+            let edge = self.edges[pair[0]].iter()
+                                          .filter(|edge| edge.to == pair[1])
+                                          .min_by_key(|edge| edge.cost)
+                                          .expect("route follows graph edges");
+            if regions.last() != Some(&edge.region) {
+                regions.push(edge.region);
+            }
+            if pair[1] >= region_count {
+                chokes.push(pair[1] - region_count);
+            }
+        }
+
+        Some(RegionRoute { regions,
+                           chokes,
+                           distance: cost as f32 / MULTF32 })
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl RegionConnection {
+    #[getter(choke)]
+    fn get_choke(&self) -> usize { self.choke }
+
+    #[getter(regions)]
+    fn get_regions(&self) -> (usize, usize) { self.regions }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl RegionRoute {
+    #[getter(regions)]
+    fn get_regions(&self) -> Vec<usize> { self.regions.clone() }
+
+    #[getter(chokes)]
+    fn get_chokes(&self) -> Vec<usize> { self.chokes.clone() }
+
+    #[getter(distance)]
+    fn get_distance(&self) -> f32 { self.distance }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    #[getter(region_connections)]
+    fn get_region_connections(&self) -> Vec<RegionConnection> { self.region_graph.connections.clone() }
+
+    /// Ground distance between the centers of chokes that border the same region, as (first, second, distance).
+    #[getter(choke_edges)]
+    fn get_choke_edges(&self) -> Vec<(usize, usize, f32)> { self.region_graph.choke_edges() }
+
+    /// Ground distance between region centers through the chokes as `distances[start][end]`, `inf` when not
+    /// connected.
+    #[getter(region_distances)]
+    fn get_region_distances(&self) -> Vec<Vec<f32>> { self.region_graph.distances.clone() }
+
+    #[name = "region_neighbours"]
+    fn region_neighbours_py(&self, region: usize) -> Vec<(usize, usize)> { self.region_graph.neighbours(region) }
+
+    /// Regions and chokes on the way between the regions of two positions.
+    #[name = "region_route"]
+    fn region_route_py(&self, start: (f32, f32), end: (f32, f32)) -> Option<RegionRoute> {
+        self.region_route(start, end)
+    }
+}
+
+impl Map {
+    /// Regions and chokes on the way between the regions of two positions, None if either is not walkable
+    /// or the regions are not connected.
+    pub fn region_route(&self, start: (f32, f32), end: (f32, f32)) -> Option<RegionRoute> {
+        self.region_graph.route(self.zone_at(start)?, self.zone_at(end)?)
+    }
+
+    pub fn region_distance(&self, start: usize, end: usize) -> Option<f32> {
+        let distance = *self.region_graph.distances.get(start)?.get(end)?;
+        if distance.is_finite() {
+            Some(distance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Walkable grid of the region and its chokes, which are walkable from both sides, cut to their bounding box.
+/// Returns the grid with the position of its first cell on the map.
+fn region_grid(points: &[Vec<MapPoint>], region: &Region, chokes: &[Choke]) -> (Vec<Vec<usize>>, (usize, usize)) {
+    let choke_pixels = region.chokes.iter().flat_map(|&choke| chokes[choke].pixels.iter());
+    let ((mut x0, mut y0), (mut x1, mut y1)) = region.bounding_box;
+    for &(x, y) in choke_pixels.clone() {
+        x0 = x0.min(x);
+        y0 = y0.min(y);
+        x1 = x1.max(x);
+        y1 = y1.max(y);
+    }
+
+    let mut grid = vec![vec![0; y1 - y0 + 1]; x1 - x0 + 1];
+    for (x, column) in points.iter().enumerate().take(x1 + 1).skip(x0) {
+        for (y, point) in column.iter().enumerate().take(y1 + 1).skip(y0) {
+            if point.zone_index == region.index as i32 {
+                grid[x - x0][y - y0] = 1;
+            }
+        }
+    }
+    for &(x, y) in choke_pixels {
+        grid[x - x0][y - y0] = 1;
+    }
+
+    (grid, (x0, y0))
+}
+
+/// Distances between the region center nodes along the edges.
+fn center_distances(edges: &[Vec<Edge>], region_count: usize) -> Vec<Vec<f32>> {
+    let mut distances = Vec::with_capacity(region_count);
+    for start in 0..region_count {
+        let reached = dijkstra_all(&start, |node| successors(edges, *node));
+        let row = (0..region_count).map(|target| match reached.get(&target) {
+                                       _ if target == start => 0.0,
+                                       Some((_, cost)) => *cost as f32 / MULTF32,
+                                       None => f32::INFINITY,
+                                   })
+                                   .collect();
+        distances.push(row);
+    }
+
+    distances
+}
+
+/// Ground distance from `start` to every cell of the grid, `usize::MAX` on cells that can't be reached.
+fn walk_costs(grid: &[Vec<usize>], start: (usize, usize)) -> Vec<Vec<usize>> {
+    let mut costs = vec![vec![usize::MAX; grid[0].len()]; grid.len()];
+    let mut heap = BinaryHeap::new();
+    costs[start.0][start.1] = 0;
+    heap.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, (x, y)))) = heap.pop() {
+        if cost > costs[x][y] {
+            continue;
+        }
+
+        for (Pos(nx, ny), step) in Pos(x, y).successors(grid) {
+            if cost + step < costs[nx][ny] {
+                costs[nx][ny] = cost + step;
+                heap.push(Reverse((cost + step, (nx, ny))));
+            }
+        }
+    }
+
+    costs
+}
+
+fn add_edge(edges: &mut [Vec<Edge>], first: usize, second: usize, cost: usize, region: usize) {
+    edges[first].push(Edge { to: second, cost, region });
+    edges[second].push(Edge { to: first, cost, region });
+}
+
+fn successors(edges: &[Vec<Edge>], node: usize) -> Vec<(usize, usize)> {
+    edges[node].iter().map(|edge| (edge.to, edge.cost)).collect()
+}
+
+fn region_center(points: &[Vec<MapPoint>], region: &Region) -> (usize, usize) {
+    let ((x0, y0), (x1, y1)) = region.bounding_box;
+    let mut best = (x0, y0);
+    let mut best_distance = f32::MAX;
+
+    for (x, column) in points.iter().enumerate().take(x1 + 1).skip(x0) {
+        for (y, point) in column.iter().enumerate().take(y1 + 1).skip(y0) {
+            if point.zone_index != region.index as i32 {
+                continue;
+            }
+            let dx = x as f32 - region.centroid.0;
+            let dy = y as f32 - region.centroid.1;
+            let distance = dx * dx + dy * dy;
+            if distance < best_distance {
+                best = (x, y);
+                best_distance = distance;
+            }
+        }
+    }
+
+    best
+}
+
+fn choke_center(choke: &Choke) -> Option<(usize, usize)> {
+    let ((x0, y0), (x1, y1)) = choke.main_line;
+    let middle = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+    let distance = |pixel: &&(usize, usize)| {
+        let dx = pixel.0 as f32 - middle.0;
+        let dy = pixel.1 as f32 - middle.1;
+        dx * dx + dy * dy
+    };
+
+    choke.pixels.iter().min_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap_or(Ordering::Equal)).copied()
+}
@@ -0,0 +1,241 @@
+use crate::mapping::chokes::Choke;
+use crate::mapping::map::Map;
+use crate::mapping::map_point::MapPoint;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Areas between chokes smaller than this are merged into their neighbours.
+const MIN_REGION_AREA: usize = 16;
+
+const NEIGHBOURS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// Walkable area separated from the rest of the map by chokes, such as a main base or a natural expansion.
+#[cfg_attr(feature = "python", pyclass)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct Region {
+    /// Index in `Map.regions`, same as `zone_index` of the cells in the region
+    pub index: usize,
+    /// Number of cells
+    pub area: usize,
+    pub centroid: (f32, f32),
+    /// Lowest and highest corner of the region
+    pub bounding_box: ((usize, usize), (usize, usize)),
+    /// Most common terrain height in the region
+    pub height: usize,
+    /// Indices in `Map.chokes` of chokes on the edge of the region
+    pub chokes: Vec<usize>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Region {
+    #[getter(index)]
+    fn get_index(&self) -> usize { self.index }
+
+    #[getter(area)]
+    fn get_area(&self) -> usize { self.area }
+
+    #[getter(centroid)]
+    fn get_centroid(&self) -> (f32, f32) { self.centroid }
+
+    #[getter(bounding_box)]
+    fn get_bounding_box(&self) -> ((usize, usize), (usize, usize)) { self.bounding_box }
+
+    #[getter(height)]
+    fn get_height(&self) -> usize { self.height }
+
+    #[getter(chokes)]
+    fn get_chokes(&self) -> Vec<usize> { self.chokes.clone() }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    #[getter(regions)]
+    fn get_regions(&self) -> Vec<Region> { self.regions.clone() }
+
+    /// Region of the position, None on unwalkable cells.
+    #[name = "region_at"]
+    fn region_at_py(&self, pos: (f32, f32)) -> Option<Region> { self.region_at(pos).cloned() }
+}
+
+impl Map {
+    /// Index of the region at the position, None on unwalkable cells.
+    pub fn zone_at(&self, pos: (f32, f32)) -> Option<usize> {
+        if pos.0 < 0.0 || pos.1 < 0.0 {
+            return None;
+        }
+
+        let column = self.points.get(pos.0 as usize)?;
+        let zone_index = column.get(pos.1 as usize)?.zone_index;
+        if zone_index < 0 {
+            None
+        } else {
+            Some(zone_index as usize)
+        }
+    }
+
+    pub fn region_at(&self, pos: (f32, f32)) -> Option<&Region> { self.regions.get(self.zone_at(pos)?) }
+}
+
+/// Splits the walkable area into regions separated by chokes and sets `zone_index` of every walkable cell.
+///
+/// Choke cells are given to the region they are closest to, so each choke is split between the regions it connects.
+pub fn solve_regions(points: &mut [Vec<MapPoint>], chokes: &[Choke]) -> Vec<Region> {
+    let width = points.len();
+    let height = points[0].len();
+    let mut zone_count = 0;
+    let mut queue = VecDeque::new();
+
+    for column in points.iter_mut() {
+        for point in column.iter_mut() {
+            point.zone_index = -1;
+        }
+    }
+
+    for x in 0..width {
+        for y in 0..height {
+            if !is_region_cell(&points[x][y]) || points[x][y].zone_index != -1 || points[x][y].is_choke {
+                continue;
+            }
+
+            let cells = flood_fill(points, (x, y), zone_count, |point| !point.is_choke && point.zone_index == -1);
+            if cells.len() < MIN_REGION_AREA {
+                // Kept apart from unvisited cells so that the pocket is not filled again, assigned to a neighbour below
+                for &(cx, cy) in &cells {
+                    points[cx][cy].zone_index = -2;
+                }
+                continue;
+            }
+
+            queue.extend(cells);
+            zone_count += 1;
+        }
+    }
+
+    // Grow the regions into choke cells and small pockets
+    while let Some((x, y)) = queue.pop_front() {
+        let zone_index = points[x][y].zone_index;
+        for (nx, ny) in neighbours((x, y), width, height) {
+            if is_region_cell(&points[nx][ny]) && points[nx][ny].zone_index < 0 {
+                points[nx][ny].zone_index = zone_index;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    // Walkable areas that don't touch any region, such as small islands
+    for x in 0..width {
+        for y in 0..height {
+            if is_region_cell(&points[x][y]) && points[x][y].zone_index < 0 {
+                flood_fill(points, (x, y), zone_count, |point| point.zone_index < 0);
+                zone_count += 1;
+            }
+        }
+    }
+
+    collect_regions(points, chokes)
+}
+
+/// Region statistics from the `zone_index` of the map points.
+pub fn collect_regions(points: &[Vec<MapPoint>], chokes: &[Choke]) -> Vec<Region> {
+    let width = points.len();
+    let height = points[0].len();
+    let count = points.iter().flatten().map(|point| point.zone_index + 1).max().unwrap_or(0).max(0) as usize;
+
+    let mut sums = vec![(0usize, 0usize); count];
+    let mut heights = vec![HashMap::<usize, usize>::new(); count];
+    let mut regions: Vec<Region> = (0..count).map(|index| Region { index,
+                                                                  area: 0,
+                                                                  centroid: (0.0, 0.0),
+                                                                  bounding_box: ((width, height), (0, 0)),
+                                                                  height: 0,
+                                                                  chokes: Vec::new() })
+                                             .collect();
+
+    for (x, column) in points.iter().enumerate() {
+        for (y, point) in column.iter().enumerate() {
+            if point.zone_index < 0 {
+                continue;
+            }
+
+            let index = point.zone_index as usize;
+            let region = &mut regions[index];
+            let (low, high) = region.bounding_box;
+            region.area += 1;
+            region.bounding_box = ((low.0.min(x), low.1.min(y)), (high.0.max(x), high.1.max(y)));
+            sums[index] = (sums[index].0 + x, sums[index].1 + y);
+            *heights[index].entry(point.height).or_insert(0) += 1;
+        }
+    }
+
+    for (index, region) in regions.iter_mut().enumerate() {
+        if region.area > 0 {
+            region.centroid = (sums[index].0 as f32 / region.area as f32, sums[index].1 as f32 / region.area as f32);
+        }
+        // Ties are broken by the lower height to keep the result stable
+        region.height = heights[index].iter()
+                                      .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                                      .map(|(height, _)| *height)
+                                      .unwrap_or(0);
+    }
+
+    for (choke_index, choke) in chokes.iter().enumerate() {
+        let mut zones = Vec::new();
+        for &pixel in &choke.pixels {
+            for (x, y) in neighbours(pixel, width, height).chain(std::iter::once(pixel)) {
+                let zone_index = points[x][y].zone_index;
+                if zone_index >= 0 && !zones.contains(&(zone_index as usize)) {
+                    zones.push(zone_index as usize);
+                }
+            }
+        }
+
+        for zone_index in zones {
+            regions[zone_index].chokes.push(choke_index);
+        }
+    }
+
+    regions
+}
+
+#[inline]
+fn is_region_cell(point: &MapPoint) -> bool { point.walkable && point.pathable }
+
+fn neighbours(pos: (usize, usize), width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+    NEIGHBOURS.iter().filter_map(move |(dx, dy)| {
+                         let x = pos.0 as isize + dx;
+                         let y = pos.1 as isize + dy;
+                         if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+                             None
+                         } else {
+                             Some((x as usize, y as usize))
+                         }
+                     })
+}
+
+/// Sets `zone_index` of the 4-connected walkable cells from `start` that pass `filter`, which must reject `zone_index`.
+fn flood_fill<F>(points: &mut [Vec<MapPoint>], start: (usize, usize), zone_index: i32, filter: F) -> Vec<(usize, usize)>
+    where F: Fn(&MapPoint) -> bool {
+    let width = points.len();
+    let height = points[0].len();
+    let mut cells = Vec::new();
+    let mut stack = vec![start];
+    points[start.0][start.1].zone_index = zone_index;
+
+    while let Some(pos) = stack.pop() {
+        cells.push(pos);
+        for (x, y) in neighbours(pos, width, height) {
+            if is_region_cell(&points[x][y]) && filter(&points[x][y]) {
+                points[x][y].zone_index = zone_index;
+                stack.push((x, y));
+            }
+        }
+    }
+
+    cells
+}
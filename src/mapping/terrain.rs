@@ -0,0 +1,157 @@
+use crate::mapping::map::Map;
+#[cfg(feature = "python")]
+use crate::numpy_grid::{grid_to_array, Orientation};
+#[cfg(feature = "python")]
+use numpy::PyArray2;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Difference between terrain levels in the height map.
+///
+/// Levels are 15.9375 apart in standard height maps, 2 in world units. Because of rounding the difference
+/// between neighbouring cells on two levels is exactly 16.
+pub const LEVEL_DIFFERENCE: usize = 16;
+
+const LEVEL_STEP: f32 = 255.0 / 16.0;
+/// World z of height 0 and 255 in the height map.
+const WORLD_HEIGHT_RANGE: (f32, f32) = (-16.0, 16.0);
+
+/// Terrain level of a height map value, ramps are on the level closest to their height.
+pub fn height_to_level(height: usize) -> usize { (height as f32 / LEVEL_STEP).round() as usize }
+
+/// World z of a height map value, the same conversion python-sc2 uses.
+pub fn height_to_world(height: usize) -> f32 {
+    WORLD_HEIGHT_RANGE.0 + (WORLD_HEIGHT_RANGE.1 - WORLD_HEIGHT_RANGE.0) * height as f32 / 255.0
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Terrain level of every cell as numpy array.
+    #[getter(terrain_levels)]
+    fn get_terrain_levels<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.terrain_levels(), Orientation::XY)
+    }
+
+    #[name = "terrain_level"]
+    fn terrain_level_py(&self, pos: (f32, f32)) -> Option<usize> { self.terrain_level(pos) }
+
+    #[name = "world_height"]
+    fn world_height_py(&self, pos: (f32, f32)) -> Option<f32> { self.world_height(pos) }
+
+    /// Whether the first position is on a higher terrain level than the second.
+    #[name = "is_high_ground_over"]
+    fn is_high_ground_over_py(&self, high: (f32, f32), low: (f32, f32)) -> bool {
+        self.is_high_ground_over(high, low)
+    }
+
+    /// Walkable cells within the distance that are on a higher terrain level than the position.
+    #[name = "overlooking_cells"]
+    fn overlooking_cells_py(&self, py: Python, pos: (f32, f32), distance: f32) -> Vec<(usize, usize)> {
+        py.allow_threads(|| self.overlooking_cells(pos, distance))
+    }
+
+    /// Number of overlooking cells within the distance of every cell as numpy array.
+    #[name = "overlooking_layer"]
+    fn overlooking_layer_py<'py>(&self, py: Python<'py>, distance: f32) -> PyResult<&'py PyArray2<usize>> {
+        let layer = py.allow_threads(|| self.overlooking_layer(distance));
+        grid_to_array(py, &layer, Orientation::XY)
+    }
+}
+
+impl Map {
+    /// Terrain level of every cell.
+    pub fn terrain_levels(&self) -> Vec<Vec<usize>> {
+        self.points.iter().map(|column| column.iter().map(|point| height_to_level(point.height)).collect()).collect()
+    }
+
+    /// Terrain level at the position, `None` outside the map.
+    pub fn terrain_level(&self, pos: (f32, f32)) -> Option<usize> { self.height_at(pos).map(height_to_level) }
+
+    /// World z at the position, `None` outside the map.
+    pub fn world_height(&self, pos: (f32, f32)) -> Option<f32> { self.height_at(pos).map(height_to_world) }
+
+    /// Whether the first position is on a higher terrain level than the second, units below can't see up
+    /// without vision from above.
+    pub fn is_high_ground_over(&self, high: (f32, f32), low: (f32, f32)) -> bool {
+        match (self.terrain_level(high), self.terrain_level(low)) {
+            (Some(high), Some(low)) => high > low,
+            _ => false,
+        }
+    }
+
+    /// Walkable cells within the distance that are on a higher terrain level than the position,
+    /// such as siege positions and spots that have vision over it.
+    pub fn overlooking_cells(&self, pos: (f32, f32), distance: f32) -> Vec<(usize, usize)> {
+        let level = match self.terrain_level(pos) {
+            Some(level) => level,
+            None => return Vec::new(),
+        };
+
+        let width = self.points.len();
+        let height = self.points[0].len();
+        let x_start = (pos.0 - distance).floor().max(0.0) as usize;
+        let y_start = (pos.1 - distance).floor().max(0.0) as usize;
+        let x_end = ((pos.0 + distance).ceil().max(0.0) as usize + 1).min(width);
+        let y_end = ((pos.1 + distance).ceil().max(0.0) as usize + 1).min(height);
+        let mut cells = Vec::new();
+
+        for (x, column) in self.points.iter().enumerate().take(x_end).skip(x_start) {
+            for (y, point) in column.iter().enumerate().take(y_end).skip(y_start) {
+                let cell_distance = (x as f32 + 0.5 - pos.0).hypot(y as f32 + 0.5 - pos.1);
+                if point.walkable && cell_distance <= distance && height_to_level(point.height) > level {
+                    cells.push((x, y));
+                }
+            }
+        }
+
+        cells
+    }
+
+    /// Number of walkable cells within the distance on a higher terrain level than each cell, the same
+    /// as the length of `overlooking_cells` at the cell center.
+    pub fn overlooking_layer(&self, distance: f32) -> Vec<Vec<usize>> {
+        let levels = self.terrain_levels();
+        let width = levels.len();
+        let height = levels[0].len();
+        let reach = distance.max(0.0).floor() as isize;
+        let mut offsets = Vec::new();
+
+        for dx in -reach..=reach {
+            for dy in -reach..=reach {
+                if (dx as f32).hypot(dy as f32) <= distance {
+                    offsets.push((dx, dy));
+                }
+            }
+        }
+
+        let mut layer = vec![vec![0; height]; width];
+        for x in 0..width {
+            for y in 0..height {
+                let level = levels[x][y];
+                layer[x][y] = offsets.iter()
+                                     .filter(|&&(dx, dy)| {
+                                         let nx = x as isize + dx;
+                                         let ny = y as isize + dy;
+                                         nx >= 0
+                                         && ny >= 0
+                                         && (nx as usize) < width
+                                         && (ny as usize) < height
+                                         && self.points[nx as usize][ny as usize].walkable
+                                         && levels[nx as usize][ny as usize] > level
+                                     })
+                                     .count();
+            }
+        }
+
+        layer
+    }
+
+    fn height_at(&self, pos: (f32, f32)) -> Option<usize> {
+        if pos.0 < 0.0 || pos.1 < 0.0 {
+            return None;
+        }
+
+        Some(self.points.get(pos.0 as usize)?.get(pos.1 as usize)?.height)
+    }
+}
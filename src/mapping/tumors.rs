@@ -0,0 +1,180 @@
+use crate::mapping::map::Map;
+use crate::mapping::mappings::MapType;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+
+/// Creep tumors spread creep within this distance.
+pub const TUMOR_SPREAD_RADIUS: f32 = 10.0;
+/// Creep tumors can place a new tumor within this distance.
+pub const TUMOR_CAST_RANGE: f32 = 10.0;
+/// Creep within this distance of the attack path counts as creep along the path.
+const PATH_CORRIDOR: f32 = 5.0;
+/// New creep along the path is worth this many cells of new creep elsewhere.
+const PATH_WEIGHT: usize = 4;
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Next position for each of the tumors that spreads creep towards the target, as (tumor, position) pairs.
+    #[name = "plan_creep_tumors"]
+    #[args(max_influence = "0")]
+    fn plan_creep_tumors_py(&self,
+                            py: Python,
+                            tumors: Vec<(f32, f32)>,
+                            target: (f32, f32),
+                            max_influence: usize)
+                            -> Vec<((f32, f32), (f32, f32))> {
+        py.allow_threads(|| self.plan_creep_tumors(&tumors, target, max_influence))
+    }
+}
+
+impl Map {
+    /// Picks the next position for each tumor that can spread, as (tumor, position) pairs.
+    ///
+    /// Positions are on creep, placeable and within walking distance `TUMOR_CAST_RANGE` of the tumor, with at most
+    /// `max_influence` ground influence above normal. The tumor with the best position picks first and the creep it
+    /// would add counts as covered for the next tumors. Positions are scored by the new creep they add, where creep
+    /// near the ground path from the tumor closest to the target counts `PATH_WEIGHT` times, ties go to the position
+    /// closest to the target. Tumors without any new creep to add are left out.
+    pub fn plan_creep_tumors(&self,
+                             tumors: &[(f32, f32)],
+                             target: (f32, f32),
+                             max_influence: usize)
+                             -> Vec<((f32, f32), (f32, f32))> {
+        let distance_to = |pos: (f32, f32)| (pos.0 - target.0).hypot(pos.1 - target.1);
+        let closest =
+            tumors.iter().min_by(|a, b| distance_to(**a).partial_cmp(&distance_to(**b)).unwrap_or(Ordering::Equal));
+        let front = match closest {
+            Some(front) => *front,
+            None => return Vec::new(),
+        };
+
+        let near_path = self.path_corridor(front, target);
+        let mut creep = self.creep.clone();
+        let mut taken: Vec<(usize, usize)> = tumors.iter().map(|pos| (pos.0 as usize, pos.1 as usize)).collect();
+        let mut candidates: Vec<(usize, Vec<(usize, usize)>)> =
+            tumors.iter().enumerate().map(|(index, tumor)| (index, self.tumor_spots(*tumor, max_influence))).collect();
+        let mut plan = Vec::new();
+
+        while !candidates.is_empty() {
+            // Best remaining spot of every tumor, with the score it has on the creep planned so far
+            let mut best: Option<(usize, (usize, usize), usize, f32)> = None;
+            for (slot, (_, spots)) in candidates.iter().enumerate() {
+                for &spot in spots.iter().filter(|spot| !taken.contains(spot)) {
+                    let (on_path, total) = self.new_creep(&creep, &near_path, spot);
+                    let score = PATH_WEIGHT * on_path + total;
+                    let distance = distance_to((spot.0 as f32 + 0.5, spot.1 as f32 + 0.5));
+                    let better = match best {
+                        Some((_, _, best_score, best_distance)) => {
+                            score > best_score || score == best_score && distance < best_distance
+                        }
+                        None => true,
+                    };
+                    if better {
+                        best = Some((slot, spot, score, distance));
+                    }
+                }
+            }
+
+            let (slot, spot) = match best {
+                Some((slot, spot, score, _)) if score > 0 => (slot, spot),
+                _ => break,
+            };
+
+            let (index, _) = candidates.remove(slot);
+            self.spread_creep(&mut creep, spot);
+            taken.push(spot);
+            plan.push((tumors[index], (spot.0 as f32 + 0.5, spot.1 as f32 + 0.5)));
+        }
+
+        plan
+    }
+
+    /// Cells where the tumor can place its next tumor.
+    fn tumor_spots(&self, tumor: (f32, f32), max_influence: usize) -> Vec<(usize, usize)> {
+        let normal_influence = self.ground_pathing.normal_influence();
+        let mut spots: Vec<(usize, usize)> =
+            self.ground_pathing
+                .djiktra(tumor, TUMOR_CAST_RANGE)
+                .into_iter()
+                .filter(|&(_, distance)| distance <= TUMOR_CAST_RANGE)
+                .map(|(pos, _)| pos)
+                .filter(|&(x, y)| {
+                    let value = self.ground_pathing.map[x][y];
+                    self.creep[x][y] > 0
+                    && self.placement[x][y] > 0
+                    && value > 0
+                    && value.saturating_sub(normal_influence) <= max_influence
+                })
+                .collect();
+        // Dijkstra results come from a hash map
+        spots.sort();
+        spots
+    }
+
+    /// Cells within `PATH_CORRIDOR` of the ground path from the start to the target.
+    fn path_corridor(&self, start: (f32, f32), target: (f32, f32)) -> Vec<Vec<bool>> {
+        let width = self.ground_pathing.width;
+        let height = self.ground_pathing.height;
+        let mut corridor = vec![vec![false; height]; width];
+        let (path, _) = self.find_path(MapType::Ground, start, target, Some(1));
+
+        for (px, py) in path {
+            for (x, y) in cells_in_radius((px as f32 + 0.5, py as f32 + 0.5), PATH_CORRIDOR, width, height) {
+                corridor[x][y] = true;
+            }
+        }
+
+        corridor
+    }
+
+    /// Walkable cells without creep that a tumor at the spot would cover, near the path and in total.
+    fn new_creep(&self, creep: &[Vec<usize>], near_path: &[Vec<bool>], spot: (usize, usize)) -> (usize, usize) {
+        let center = (spot.0 as f32 + 0.5, spot.1 as f32 + 0.5);
+        let width = self.ground_pathing.width;
+        let height = self.ground_pathing.height;
+        let mut on_path = 0;
+        let mut total = 0;
+
+        for (x, y) in cells_in_radius(center, TUMOR_SPREAD_RADIUS, width, height) {
+            if creep[x][y] == 0 && self.points[x][y].walkable {
+                total += 1;
+                if near_path[x][y] {
+                    on_path += 1;
+                }
+            }
+        }
+
+        (on_path, total)
+    }
+
+    fn spread_creep(&self, creep: &mut [Vec<usize>], spot: (usize, usize)) {
+        let center = (spot.0 as f32 + 0.5, spot.1 as f32 + 0.5);
+        let width = self.ground_pathing.width;
+        let height = self.ground_pathing.height;
+        for (x, y) in cells_in_radius(center, TUMOR_SPREAD_RADIUS, width, height) {
+            if self.points[x][y].walkable {
+                creep[x][y] = 1;
+            }
+        }
+    }
+}
+
+fn cells_in_radius(center: (f32, f32), radius: f32, width: usize, height: usize) -> Vec<(usize, usize)> {
+    let x_start = (center.0 - radius).floor().max(0.0) as usize;
+    let y_start = (center.1 - radius).floor().max(0.0) as usize;
+    let x_end = ((center.0 + radius).ceil().max(0.0) as usize).min(width);
+    let y_end = ((center.1 + radius).ceil().max(0.0) as usize).min(height);
+    let mut cells = Vec::new();
+
+    for x in x_start..x_end {
+        for y in y_start..y_end {
+            if (x as f32 + 0.5 - center.0).hypot(y as f32 + 0.5 - center.1) <= radius {
+                cells.push((x, y));
+            }
+        }
+    }
+
+    cells
+}
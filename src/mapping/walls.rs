@@ -0,0 +1,316 @@
+use crate::mapping::map::Map;
+use crate::path_find::pos::Pos;
+use crate::path_find::rectangle::Rectangle;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Buildings of a wall are placed on cells at most this far from the top of the ramp or the middle of the choke,
+/// distances are between cell coordinates like `Ramp.top_center`.
+const BUILD_RADIUS: f32 = 6.0;
+/// Reachability through the wall is checked in a square this far around it.
+const WINDOW_RADIUS: usize = 14;
+
+/// Cells on both sides of the wall, buildings can't be placed on them.
+struct WallArea {
+    center: (f32, f32),
+    buildable: Vec<(usize, usize)>,
+    inside: Vec<(usize, usize)>,
+    outside: Vec<(usize, usize)>,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Building centers that wall off the top of a ramp, in the order of `buildings`.
+    /// `gap` is the size of units that can still pass, 0 seals the ramp.
+    #[name = "wall_ramp"]
+    #[args(gap = "0")]
+    fn wall_ramp_py(&self,
+                    py: Python,
+                    ramp: usize,
+                    buildings: Vec<(usize, usize)>,
+                    gap: usize)
+                    -> Option<Vec<(f32, f32)>> {
+        py.allow_threads(|| self.wall_ramp(ramp, &buildings, gap))
+    }
+
+    /// Building centers that wall off a choke, in the order of `buildings`.
+    /// `gap` is the size of units that can still pass, 0 seals the choke.
+    #[name = "wall_choke"]
+    #[args(gap = "0")]
+    fn wall_choke_py(&self,
+                     py: Python,
+                     choke: usize,
+                     buildings: Vec<(usize, usize)>,
+                     gap: usize)
+                     -> Option<Vec<(f32, f32)>> {
+        py.allow_threads(|| self.wall_choke(choke, &buildings, gap))
+    }
+}
+
+impl Map {
+    /// Places buildings of the given sizes on the upper side of `Map.ramps[ramp]` so that ground units can't get
+    /// from the ramp to the upper ground, or only units that fit through a gap of `gap` cells.
+    ///
+    /// Returns the building centers in the order of `buildings`, None if no such wall can be built.
+    pub fn wall_ramp(&self, ramp: usize, buildings: &[(usize, usize)], gap: usize) -> Option<Vec<(f32, f32)>> {
+        let ramp = self.ramps.get(ramp)?;
+        let is_upper = |x: usize, y: usize| self.points[x][y].height * 2 > ramp.upper_height + ramp.lower_height;
+        let center = ramp.top_center;
+
+        let mut area = WallArea { center,
+                                  buildable: Vec::new(),
+                                  inside: Vec::new(),
+                                  outside: ramp.cells.clone() };
+        for (x, y) in self.wall_window(center) {
+            if !is_upper(x, y) || self.placement[x][y] == 0 {
+                continue;
+            }
+            if distance((x, y), center) <= BUILD_RADIUS {
+                area.buildable.push((x, y));
+            } else {
+                area.inside.push((x, y));
+            }
+        }
+
+        self.find_wall(&area, buildings, gap)
+    }
+
+    /// Places buildings of the given sizes around the middle of `Map.chokes[choke]` so that ground units can't get
+    /// from one region next to the choke to the other, or only units that fit through a gap of `gap` cells.
+    ///
+    /// Returns the building centers in the order of `buildings`, None if no such wall can be built.
+    pub fn wall_choke(&self, choke: usize, buildings: &[(usize, usize)], gap: usize) -> Option<Vec<(f32, f32)>> {
+        let ((x0, y0), (x1, y1)) = self.chokes.get(choke)?.main_line;
+        let connection = self.region_graph.connections.iter().find(|connection| connection.choke == choke)?;
+        let center = ((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+
+        let mut area = WallArea { center,
+                                  buildable: Vec::new(),
+                                  inside: Vec::new(),
+                                  outside: Vec::new() };
+        for (x, y) in self.wall_window(center) {
+            let zone_index = self.points[x][y].zone_index;
+            if distance((x, y), center) <= BUILD_RADIUS {
+                if self.placement[x][y] > 0 {
+                    area.buildable.push((x, y));
+                }
+            } else if zone_index == connection.regions.0 as i32 {
+                area.inside.push((x, y));
+            } else if zone_index == connection.regions.1 as i32 {
+                area.outside.push((x, y));
+            }
+        }
+
+        self.find_wall(&area, buildings, gap)
+    }
+
+    fn wall_window(&self, center: (f32, f32)) -> impl Iterator<Item = (usize, usize)> {
+        let (x0, y0, x1, y1) = self.window_bounds(center);
+        (x0..x1).flat_map(move |x| (y0..y1).map(move |y| (x, y)))
+    }
+
+    fn window_bounds(&self, center: (f32, f32)) -> (usize, usize, usize, usize) {
+        let x = center.0 as usize;
+        let y = center.1 as usize;
+        (x.saturating_sub(WINDOW_RADIUS),
+         y.saturating_sub(WINDOW_RADIUS),
+         (x + WINDOW_RADIUS + 1).min(self.ground_pathing.width),
+         (y + WINDOW_RADIUS + 1).min(self.ground_pathing.height))
+    }
+
+    fn find_wall(&self, area: &WallArea, buildings: &[(usize, usize)], gap: usize) -> Option<Vec<(f32, f32)>> {
+        let (x0, y0, x1, y1) = self.window_bounds(area.center);
+        let mut open = vec![vec![false; y1 - y0]; x1 - x0];
+        let mut buildable = vec![vec![false; y1 - y0]; x1 - x0];
+        for x in x0..x1 {
+            for y in y0..y1 {
+                open[x - x0][y - y0] = self.ground_pathing.map[x][y] > 0;
+            }
+        }
+        for &(x, y) in &area.buildable {
+            buildable[x - x0][y - y0] = open[x - x0][y - y0];
+        }
+
+        let local = |cells: &[(usize, usize)]| -> Vec<(usize, usize)> {
+            cells.iter()
+                 .filter(|&&(x, y)| x0 <= x && x < x1 && y0 <= y && y < y1 && open[x - x0][y - y0])
+                 .map(|&(x, y)| (x - x0, y - y0))
+                 .collect()
+        };
+        let window = WallWindow { origin: (x0, y0),
+                                  inside: local(&area.inside),
+                                  outside: local(&area.outside),
+                                  open,
+                                  buildable,
+                                  gap };
+        if window.inside.is_empty() || window.outside.is_empty() || !window.connected(&window.open, 1) {
+            return None;
+        }
+
+        // Larger buildings first, they leave fewer places for the rest
+        let mut order: Vec<usize> = (0..buildings.len()).collect();
+        order.sort_by_key(|&index| std::cmp::Reverse(buildings[index].0 * buildings[index].1));
+        let sizes: Vec<(usize, usize)> = order.iter().map(|&index| buildings[index]).collect();
+        let candidates: Vec<Vec<Rectangle>> = sizes.iter().map(|size| window.candidates(*size, area.center)).collect();
+
+        let mut placed = Vec::new();
+        if !window.search(&sizes, &candidates, &mut placed, 0) {
+            return None;
+        }
+
+        let mut centers = vec![(0.0, 0.0); buildings.len()];
+        for ((rect, _), &index) in placed.iter().zip(&order) {
+            centers[index] = ((x0 + rect.x) as f32 + (rect.x_end - rect.x) as f32 / 2.0,
+                              (y0 + rect.y) as f32 + (rect.y_end - rect.y) as f32 / 2.0);
+        }
+        Some(centers)
+    }
+}
+
+/// Part of the map around the wall, cells are relative to `origin`.
+struct WallWindow {
+    origin: (usize, usize),
+    open: Vec<Vec<bool>>,
+    buildable: Vec<Vec<bool>>,
+    inside: Vec<(usize, usize)>,
+    outside: Vec<(usize, usize)>,
+    gap: usize,
+}
+
+impl WallWindow {
+    /// Footprints of the size that fit on buildable cells, closest to the center first.
+    fn candidates(&self, size: (usize, usize), center: (f32, f32)) -> Vec<Rectangle> {
+        let width = self.open.len();
+        let height = self.open[0].len();
+        let mut candidates = Vec::new();
+
+        for x in 0..(width + 1).saturating_sub(size.0) {
+            for y in 0..(height + 1).saturating_sub(size.1) {
+                let rect = Rectangle { x,
+                                       y,
+                                       x_end: x + size.0,
+                                       y_end: y + size.1 };
+                if self.buildable[x..rect.x_end].iter().all(|column| column[y..rect.y_end].iter().all(|cell| *cell)) {
+                    candidates.push(rect);
+                }
+            }
+        }
+
+        let local_center = (center.0 - self.origin.0 as f32, center.1 - self.origin.1 as f32);
+        let rect_distance = |rect: &Rectangle| {
+            distance_f32(((rect.x + rect.x_end - 1) as f32 / 2.0, (rect.y + rect.y_end - 1) as f32 / 2.0), local_center)
+        };
+        candidates.sort_by(|a, b| rect_distance(a).partial_cmp(&rect_distance(b)).unwrap());
+        candidates
+    }
+
+    /// Places the buildings one at a time, each next to a cliff or an already placed building.
+    /// `placed` holds the footprints with their index in `candidates`.
+    fn search(&self,
+              sizes: &[(usize, usize)],
+              candidates: &[Vec<Rectangle>],
+              placed: &mut Vec<(Rectangle, usize)>,
+              depth: usize)
+              -> bool {
+        if depth == sizes.len() {
+            return self.is_wall(placed);
+        }
+
+        // Buildings of the same size are interchangeable, only one order of them is tried
+        let first = match placed.last() {
+            Some((_, index)) if depth > 0 && sizes[depth - 1] == sizes[depth] => index + 1,
+            _ => 0,
+        };
+        let max_gap = self.gap as isize;
+
+        for (index, rect) in candidates[depth].iter().enumerate().skip(first) {
+            if placed.iter().any(|(other, _)| rect.gap(other) < 0) {
+                continue;
+            }
+            if !self.near_cliff(rect) && !placed.iter().any(|(other, _)| rect.gap(other) <= max_gap) {
+                continue;
+            }
+
+            placed.push((rect.clone(), index));
+            if self.search(sizes, candidates, placed, depth + 1) {
+                return true;
+            }
+            placed.pop();
+        }
+
+        false
+    }
+
+    /// Unpathable cell within `gap` free cells of the footprint.
+    fn near_cliff(&self, rect: &Rectangle) -> bool {
+        let reach = self.gap + 1;
+        let x_end = (rect.x_end + reach).min(self.open.len());
+        let y_end = (rect.y_end + reach).min(self.open[0].len());
+
+        (rect.x.saturating_sub(reach)..x_end).any(|x| {
+                                                 (rect.y.saturating_sub(reach)..y_end).any(|y| !self.open[x][y])
+                                             })
+    }
+
+    fn is_wall(&self, placed: &[(Rectangle, usize)]) -> bool {
+        let mut open = self.open.clone();
+        for (rect, _) in placed {
+            for column in &mut open[rect.x..rect.x_end] {
+                for cell in &mut column[rect.y..rect.y_end] {
+                    *cell = false;
+                }
+            }
+        }
+
+        if self.gap == 0 {
+            !self.connected(&open, 1)
+        } else {
+            self.connected(&open, self.gap) && !self.connected(&open, self.gap + 1)
+        }
+    }
+
+    /// Whether a unit covering `size` x `size` cells can walk from the inside to the outside.
+    fn connected(&self, open: &[Vec<bool>], size: usize) -> bool {
+        let width = open.len();
+        let height = open[0].len();
+        let mut fits = vec![vec![0; height]; width];
+        for x in 0..(width + 1).saturating_sub(size) {
+            for y in 0..(height + 1).saturating_sub(size) {
+                if open[x..x + size].iter().all(|column| column[y..y + size].iter().all(|cell| *cell)) {
+                    fits[x][y] = 1;
+                }
+            }
+        }
+
+        let mut reached = vec![vec![false; height]; width];
+        let mut stack: Vec<Pos> = Vec::new();
+        for &(x, y) in &self.inside {
+            if fits[x][y] > 0 && !reached[x][y] {
+                reached[x][y] = true;
+                stack.push(Pos(x, y));
+            }
+        }
+
+        while let Some(pos) = stack.pop() {
+            for (Pos(x, y), _) in pos.successors(&fits) {
+                if !reached[x][y] {
+                    reached[x][y] = true;
+                    stack.push(Pos(x, y));
+                }
+            }
+        }
+
+        self.outside.iter().any(|&(x, y)| reached[x][y])
+    }
+}
+
+#[inline]
+fn distance(cell: (usize, usize), center: (f32, f32)) -> f32 {
+    distance_f32((cell.0 as f32, cell.1 as f32), center)
+}
+
+#[inline]
+fn distance_f32(first: (f32, f32), second: (f32, f32)) -> f32 {
+    ((first.0 - second.0).powi(2) + (first.1 - second.1).powi(2)).sqrt()
+}
@@ -0,0 +1,116 @@
+use crate::mapping::map::Map;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::cmp::Ordering;
+
+/// Power radius of a pylon.
+pub const PYLON_POWER_RADIUS: f32 = 6.5;
+/// Power radius of a warp prism in phasing mode.
+pub const WARP_PRISM_POWER_RADIUS: f32 = 3.75;
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Cells units can be warped in on, `sources` are the positions and power radiuses of pylons and warp prisms.
+    #[name = "warp_in_cells"]
+    #[args(occupied = "None")]
+    fn warp_in_cells_py(&self,
+                        py: Python,
+                        sources: Vec<((f32, f32), f32)>,
+                        occupied: Option<Vec<(f32, f32)>>)
+                        -> Vec<(usize, usize)> {
+        let occupied = occupied.unwrap_or_default();
+        py.allow_threads(|| self.warp_in_cells(&sources, &occupied))
+    }
+
+    /// Up to `count` warp-in positions at least `spacing` apart, lowest ground influence first and then closest
+    /// to the target.
+    #[name = "find_warp_ins"]
+    #[args(spacing = "1.0", occupied = "None")]
+    #[allow(clippy::too_many_arguments)]
+    fn find_warp_ins_py(&self,
+                        py: Python,
+                        sources: Vec<((f32, f32), f32)>,
+                        target: (f32, f32),
+                        count: usize,
+                        spacing: f32,
+                        occupied: Option<Vec<(f32, f32)>>)
+                        -> Vec<(f32, f32)> {
+        let occupied = occupied.unwrap_or_default();
+        py.allow_threads(|| self.find_warp_ins(&sources, &occupied, target, count, spacing))
+    }
+}
+
+impl Map {
+    /// Cells within the power radius of any of the sources.
+    pub fn power_field(&self, sources: &[((f32, f32), f32)]) -> Vec<(usize, usize)> {
+        let width = self.ground_pathing.width;
+        let height = self.ground_pathing.height;
+        let mut cells = Vec::new();
+
+        for &(center, radius) in sources {
+            let x_start = (center.0 - radius).floor().max(0.0) as usize;
+            let y_start = (center.1 - radius).floor().max(0.0) as usize;
+            let x_end = ((center.0 + radius).ceil().max(0.0) as usize).min(width);
+            let y_end = ((center.1 + radius).ceil().max(0.0) as usize).min(height);
+
+            for x in x_start..x_end {
+                for y in y_start..y_end {
+                    let distance = (x as f32 + 0.5 - center.0).hypot(y as f32 + 0.5 - center.1);
+                    if distance <= radius {
+                        cells.push((x, y));
+                    }
+                }
+            }
+        }
+
+        // Fields of several sources overlap
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    /// Powered cells that are pathable and placeable, without blocks, obstacles or the `occupied` positions.
+    pub fn warp_in_cells(&self, sources: &[((f32, f32), f32)], occupied: &[(f32, f32)]) -> Vec<(usize, usize)> {
+        self.power_field(sources)
+            .into_iter()
+            .filter(|&(x, y)| self.ground_pathing.map[x][y] > 0 && self.placement[x][y] > 0)
+            .filter(|&(x, y)| !occupied.iter().any(|pos| pos.0 as usize == x && pos.1 as usize == y))
+            .collect()
+    }
+
+    /// Up to `count` warp-in positions that are at least `spacing` apart from each other.
+    ///
+    /// Cells with the lowest ground influence are picked first, ties go to the cell closest to the target.
+    pub fn find_warp_ins(&self,
+                         sources: &[((f32, f32), f32)],
+                         occupied: &[(f32, f32)],
+                         target: (f32, f32),
+                         count: usize,
+                         spacing: f32)
+                         -> Vec<(f32, f32)> {
+        let normal_influence = self.ground_pathing.normal_influence();
+        let mut candidates: Vec<((f32, f32), usize, f32)> =
+            self.warp_in_cells(sources, occupied)
+                .into_iter()
+                .map(|(x, y)| {
+                    let position = (x as f32 + 0.5, y as f32 + 0.5);
+                    let influence = self.ground_pathing.map[x][y].saturating_sub(normal_influence);
+                    (position, influence, (position.0 - target.0).hypot(position.1 - target.1))
+                })
+                .collect();
+        candidates.sort_by(|a, b| a.1.cmp(&b.1).then(a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal)));
+
+        let mut picked: Vec<(f32, f32)> = Vec::new();
+        for (position, _, _) in candidates {
+            if picked.len() >= count {
+                break;
+            }
+            if picked.iter().all(|other| (position.0 - other.0).hypot(position.1 - other.1) >= spacing) {
+                picked.push(position);
+            }
+        }
+
+        picked
+    }
+}
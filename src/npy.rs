@@ -0,0 +1,156 @@
+use crate::binary::invalid_data;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// Element types of two dimensional grids, other numpy dtypes are rejected.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Kind {
+    Bool,
+    Unsigned,
+    Signed,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct DType {
+    kind: Kind,
+    size: usize,
+    big_endian: bool,
+}
+
+impl DType {
+    fn parse(descr: &str) -> io::Result<DType> {
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('>') => true,
+            Some('<') | Some('|') | Some('=') => false,
+            _ => return Err(invalid_data(&format!("unsupported dtype '{}'", descr))),
+        };
+        let kind = match chars.next() {
+            Some('b') => Kind::Bool,
+            Some('u') => Kind::Unsigned,
+            Some('i') => Kind::Signed,
+            _ => return Err(invalid_data(&format!("unsupported dtype '{}'", descr))),
+        };
+        let size = match chars.as_str() {
+            "1" => 1,
+            "2" => 2,
+            "4" => 4,
+            "8" => 8,
+            _ => return Err(invalid_data(&format!("unsupported dtype '{}'", descr))),
+        };
+        if kind == Kind::Bool && size != 1 {
+            return Err(invalid_data(&format!("unsupported dtype '{}'", descr)));
+        }
+
+        Ok(DType { kind, size, big_endian })
+    }
+
+    /// Negative values are treated as 0 like in the python bindings.
+    fn read(&self, bytes: &[u8]) -> usize {
+        let mut buffer = [0u8; 8];
+        if self.big_endian {
+            buffer[8 - self.size..].copy_from_slice(bytes);
+            buffer.reverse();
+        } else {
+            buffer[..self.size].copy_from_slice(bytes);
+        }
+        let value = u64::from_le_bytes(buffer);
+
+        match self.kind {
+            Kind::Bool => (value != 0) as usize,
+            Kind::Unsigned => value as usize,
+            Kind::Signed => {
+                let shift = 64 - 8 * self.size as u32;
+                let signed = ((value << shift) as i64) >> shift;
+                if signed > 0 {
+                    signed as usize
+                } else {
+                    0
+                }
+            }
+        }
+    }
+}
+
+/// Loads a grid saved with `numpy.save` into the `grid[x][y]` layout used by `Map::new` and `PathFind`.
+///
+/// The array is expected in the game layout `array[y][x]`, as saved from python-sc2 `PixelMap.data_numpy`.
+pub fn load_grid<P: AsRef<Path>>(path: P) -> io::Result<Vec<Vec<usize>>> { parse_grid(&fs::read(path)?) }
+
+/// Same as `load_grid` for the contents of a `.npy` file.
+pub fn parse_grid(data: &[u8]) -> io::Result<Vec<Vec<usize>>> {
+    if data.len() < 10 || &data[..6] != MAGIC {
+        return Err(invalid_data("not a .npy file"));
+    }
+
+    let (header_len, header_start) = match data[6] {
+        1 => (u16::from_le_bytes([data[8], data[9]]) as usize, 10),
+        2 | 3 if data.len() >= 12 => (u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize, 12),
+        version => return Err(invalid_data(&format!("unsupported .npy version {}", version))),
+    };
+    let header = data.get(header_start..header_start + header_len)
+                     .ok_or_else(|| invalid_data("truncated .npy header"))?;
+    let header = String::from_utf8_lossy(header);
+
+    let dtype = DType::parse(&parse_string(&header, "descr")?)?;
+    let fortran_order = header_value(&header, "fortran_order")?.starts_with("True");
+    let shape = parse_shape(&header)?;
+    if shape.len() != 2 || shape[0] == 0 || shape[1] == 0 {
+        return Err(invalid_data("grid must be two dimensional and not empty"));
+    }
+
+    let (rows, columns) = (shape[0], shape[1]);
+    let cells = &data[header_start + header_len..];
+    let count = rows.checked_mul(columns)
+                    .filter(|count| count.saturating_mul(dtype.size) <= cells.len())
+                    .ok_or_else(|| invalid_data("truncated .npy data"))?;
+
+    let mut grid = vec![vec![0; rows]; columns];
+    for (index, bytes) in cells.chunks_exact(dtype.size).take(count).enumerate() {
+        let (y, x) = if fortran_order {
+            (index % rows, index / rows)
+        } else {
+            (index / columns, index % columns)
+        };
+        grid[x][y] = dtype.read(bytes);
+    }
+
+    Ok(grid)
+}
+
+// Text after `'key':` in the header dictionary
+fn header_value<'a>(header: &'a str, key: &str) -> io::Result<&'a str> {
+    for quoted in &[format!("'{}':", key), format!("\"{}\":", key)] {
+        if let Some(start) = header.find(quoted.as_str()) {
+            return Ok(header[start + quoted.len()..].trim_start());
+        }
+    }
+
+    Err(invalid_data(&format!("missing '{}' in .npy header", key)))
+}
+
+fn parse_string(header: &str, key: &str) -> io::Result<String> {
+    let value = header_value(header, key)?;
+    let quote = value.chars().next().filter(|c| *c == '\'' || *c == '"');
+    let quote = quote.ok_or_else(|| invalid_data(&format!("invalid '{}' in .npy header", key)))?;
+
+    match value[1..].find(quote) {
+        Some(end) => Ok(value[1..=end].to_string()),
+        None => Err(invalid_data(&format!("invalid '{}' in .npy header", key))),
+    }
+}
+
+fn parse_shape(header: &str) -> io::Result<Vec<usize>> {
+    let value = header_value(header, "shape")?;
+    let end = value.find(')').filter(|_| value.starts_with('('));
+    let end = end.ok_or_else(|| invalid_data("invalid shape in .npy header"))?;
+
+    value[1..end].split(',')
+                 .map(|part| part.trim())
+                 .filter(|part| !part.is_empty())
+                 .map(|part| part.parse::<usize>().map_err(|_| invalid_data("invalid shape in .npy header")))
+                 .collect()
+}
@@ -0,0 +1,149 @@
+use numpy::{PyArray1, PyArray2, PyReadonlyArray2};
+use pyo3::exceptions::ValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+
+/// Memory layout of a grid passed to or from Python.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// `grid[x][y]`, the layout used internally.
+    XY,
+    /// `grid[y][x]`, the layout of numpy arrays from the game (python-sc2 `PixelMap.data_numpy`).
+    YX,
+}
+
+impl Orientation {
+    pub fn parse(value: &str) -> PyResult<Orientation> {
+        match value {
+            "xy" => Ok(Orientation::XY),
+            "yx" => Ok(Orientation::YX),
+            _ => Err(ValueError::py_err(format!("orientation must be 'xy' or 'yx', got '{}'", value))),
+        }
+    }
+}
+
+macro_rules! try_extract_array {
+    ($obj:expr, $orientation:expr, $($t:ty),*) => {
+        $(
+            if let Ok(array) = $obj.extract::<PyReadonlyArray2<$t>>() {
+                let view = array.as_array();
+                let shape = view.shape();
+                let (width, height) = match $orientation {
+                    Orientation::XY => (shape[0], shape[1]),
+                    Orientation::YX => (shape[1], shape[0]),
+                };
+                if width == 0 || height == 0 {
+                    return Err(ValueError::py_err("grid must not be empty"));
+                }
+
+                let mut grid = vec![vec![0; height]; width];
+                for (x, column) in grid.iter_mut().enumerate() {
+                    for (y, value) in column.iter_mut().enumerate() {
+                        let cell = match $orientation {
+                            Orientation::XY => view[[x, y]],
+                            Orientation::YX => view[[y, x]],
+                        };
+                        *value = cell.to_usize();
+                    }
+                }
+                return Ok(grid);
+            }
+        )*
+    };
+}
+
+trait CellValue: Copy {
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_unsigned_cell {
+    ($($t:ty),*) => { $(impl CellValue for $t { fn to_usize(self) -> usize { self as usize } })* };
+}
+
+macro_rules! impl_signed_cell {
+    ($($t:ty),*) => {
+        $(impl CellValue for $t {
+            fn to_usize(self) -> usize {
+                if self > 0 as $t {
+                    self as usize
+                } else {
+                    0
+                }
+            }
+        })*
+    };
+}
+
+impl_unsigned_cell!(u8, u16, u32, u64, usize);
+impl_signed_cell!(i8, i16, i32, i64, f32, f64);
+
+impl CellValue for bool {
+    fn to_usize(self) -> usize { self as usize }
+}
+
+/// Reads a two dimensional numpy array or nested list into the internal `grid[x][y]` layout, copying the values.
+/// Negative values are treated as 0.
+pub fn extract_grid(obj: &PyAny, orientation: Orientation) -> PyResult<Vec<Vec<usize>>> {
+    // Lists are checked first so that plain Python users don't need numpy installed
+    if obj.downcast::<PyList>().is_err() && obj.downcast::<PyTuple>().is_err() {
+        try_extract_array!(obj, orientation, bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, f32, f64);
+    }
+
+    let lists: Vec<Vec<usize>> = obj.extract()?;
+    if lists.is_empty() || lists[0].is_empty() {
+        return Err(ValueError::py_err("grid must not be empty"));
+    }
+    if lists.iter().any(|row| row.len() != lists[0].len()) {
+        return Err(ValueError::py_err("grid rows must have equal length"));
+    }
+
+    match orientation {
+        Orientation::XY => Ok(lists),
+        Orientation::YX => Ok(transpose(&lists)),
+    }
+}
+
+fn transpose(grid: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut result = vec![vec![0; grid.len()]; grid[0].len()];
+    for (y, row) in grid.iter().enumerate() {
+        for (x, value) in row.iter().enumerate() {
+            result[x][y] = *value;
+        }
+    }
+    result
+}
+
+/// Copies the grid into a new numpy array marked read-only, the array doesn't follow later changes to the grid.
+///
+/// Grids are kept as separate columns, which numpy can't view as one array, and a view would be left pointing
+/// at freed memory when the grid is replaced. The copy goes through one contiguous buffer that is handed over
+/// to numpy without further copies.
+pub fn grid_to_array<'py>(py: Python<'py>,
+                          grid: &[Vec<usize>],
+                          orientation: Orientation)
+                          -> PyResult<&'py PyArray2<usize>> {
+    let width = grid.len();
+    let height = if width > 0 { grid[0].len() } else { 0 };
+    let mut flat = Vec::with_capacity(width * height);
+
+    let array = match orientation {
+        Orientation::XY => {
+            for column in grid {
+                flat.extend_from_slice(column);
+            }
+            PyArray1::from_vec(py, flat).reshape([width, height])?
+        }
+        Orientation::YX => {
+            for y in 0..height {
+                for column in grid {
+                    flat.push(column[y]);
+                }
+            }
+            PyArray1::from_vec(py, flat).reshape([height, width])?
+        }
+    };
+
+    // Writes to the copy would be lost, so don't allow them
+    array.call_method1("setflags", (false,))?;
+    Ok(array)
+}
@@ -0,0 +1,186 @@
+use crate::path_find::PathFind;
+#[cfg(feature = "python")]
+use pyo3::exceptions::ValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Names accepted by `Footprint::named`.
+pub const FOOTPRINT_NAMES: &[&str] = &["mineral_field",
+                                       "vespene_geyser",
+                                       "town_hall",
+                                       "building_2x2",
+                                       "building_3x3",
+                                       "supply_depot_lowered",
+                                       "production_with_addon",
+                                       "rocks_2x2",
+                                       "rocks_4x4",
+                                       "rocks_6x6",
+                                       "rocks_diagonal_up",
+                                       "rocks_diagonal_down"];
+
+/// Shape of a structure on the grid.
+///
+/// Cells are relative to the lowest corner of the shape, which is at `corner` from the center of the structure.
+/// A shape can block pathing and placement on different cells, town halls leave their corners pathable
+/// and lowered supply depots only block placement.
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footprint {
+    pub corner: (f32, f32),
+    /// Cells that ground units can't walk on
+    pub pathing: Vec<(usize, usize)>,
+    /// Cells that can't be built on
+    pub placement: Vec<(usize, usize)>,
+}
+
+impl Footprint {
+    pub fn new(corner: (f32, f32), pathing: Vec<(usize, usize)>, placement: Vec<(usize, usize)>) -> Footprint {
+        Footprint { corner,
+                    pathing,
+                    placement }
+    }
+
+    /// Rectangle blocking both pathing and placement, the same cells `PathFind::create_block` blocks.
+    pub fn rectangle(size: (usize, usize)) -> Footprint {
+        let cells = cut_corners(size, 0);
+        Footprint::new((-(size.0 as f32) / 2.0, -(size.1 as f32) / 2.0), cells.clone(), cells)
+    }
+
+    /// Built-in shape, see `FOOTPRINT_NAMES`. Rock shapes are approximations of the most common destructible rocks.
+    pub fn named(name: &str) -> Option<Footprint> {
+        let footprint = match name {
+            "mineral_field" => Footprint::rectangle((2, 1)),
+            "vespene_geyser" | "building_3x3" => Footprint::rectangle((3, 3)),
+            "building_2x2" | "rocks_2x2" => Footprint::rectangle((2, 2)),
+            "town_hall" => Footprint::new((-2.5, -2.5), cut_corners((5, 5), 1), cut_corners((5, 5), 0)),
+            "supply_depot_lowered" => Footprint::new((-1.0, -1.0), Vec::new(), cut_corners((2, 2), 0)),
+            "production_with_addon" => {
+                // Addon is attached to the right side with its center at (2.5, -0.5) from the building center
+                let mut cells = cut_corners((3, 3), 0);
+                cells.extend(&[(3, 0), (3, 1), (4, 0), (4, 1)]);
+                Footprint::new((-1.5, -1.5), cells.clone(), cells)
+            }
+            "rocks_4x4" => Footprint::new((-2.0, -2.0), cut_corners((4, 4), 1), cut_corners((4, 4), 1)),
+            "rocks_6x6" => Footprint::new((-3.0, -3.0), cut_corners((6, 6), 2), cut_corners((6, 6), 2)),
+            "rocks_diagonal_up" => Footprint::new((-3.0, -3.0), diagonal(true), diagonal(true)),
+            "rocks_diagonal_down" => Footprint::new((-3.0, -3.0), diagonal(false), diagonal(false)),
+            _ => return None,
+        };
+
+        Some(footprint)
+    }
+
+    /// Grid cells of the pathing or placement mask for a structure at the center, cells outside the grid are left out.
+    pub fn cells(&self,
+                 mask: &[(usize, usize)],
+                 center: (f32, f32),
+                 width: usize,
+                 height: usize)
+                 -> Vec<(usize, usize)> {
+        // Rounded the same way as `Rectangle::init_from_center`
+        let x0 = (center.0.floor() + self.corner.0).ceil() as isize;
+        let y0 = (center.1.floor() + self.corner.1).ceil() as isize;
+
+        mask.iter()
+            .map(|&(x, y)| (x0 + x as isize, y0 + y as isize))
+            .filter(|&(x, y)| x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height)
+            .map(|(x, y)| (x as usize, y as usize))
+            .collect()
+    }
+}
+
+/// Cells of a rectangle without the `cut` cells closest to each corner along the edges.
+fn cut_corners(size: (usize, usize), cut: usize) -> Vec<(usize, usize)> {
+    let mut cells = Vec::with_capacity(size.0 * size.1);
+    for x in 0..size.0 {
+        for y in 0..size.1 {
+            if x.min(size.0 - 1 - x) + y.min(size.1 - 1 - y) >= cut {
+                cells.push((x, y));
+            }
+        }
+    }
+
+    cells
+}
+
+/// Band of 6x6 rocks going up or down from the left side.
+fn diagonal(up: bool) -> Vec<(usize, usize)> {
+    cut_corners((6, 6), 0).into_iter()
+                          .filter(|&(x, y)| {
+                              let y = if up { y } else { 5 - y };
+                              (x as isize - y as isize).abs() <= 1
+                          })
+                          .collect()
+}
+
+impl PathFind {
+    /// Makes the pathing cells of the shape unpathable.
+    pub fn create_footprint_block(&mut self, footprint: &Footprint, center: (f32, f32)) {
+        for (x, y) in footprint.cells(&footprint.pathing, center, self.width, self.height) {
+            self.map[x][y] = 0;
+        }
+    }
+
+    /// Makes the pathing cells of the shape pathable again.
+    pub fn remove_footprint_block(&mut self, footprint: &Footprint, center: (f32, f32)) {
+        for (x, y) in footprint.cells(&footprint.pathing, center, self.width, self.height) {
+            self.restore_cell(x, y);
+        }
+    }
+}
+
+/// Name of a built-in shape or a `Footprint`.
+#[cfg(feature = "python")]
+pub fn extract_footprint(shape: &PyAny) -> PyResult<Footprint> {
+    if let Ok(name) = shape.extract::<&str>() {
+        return Footprint::named(name).ok_or_else(|| ValueError::py_err(format!("unknown footprint '{}'", name)));
+    }
+
+    shape.extract()
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Footprint {
+    #[new]
+    fn new_py(corner: (f32, f32), pathing: Vec<(usize, usize)>, placement: Vec<(usize, usize)>) -> Self {
+        Footprint::new(corner, pathing, placement)
+    }
+
+    #[staticmethod]
+    #[name = "rectangle"]
+    fn rectangle_py(size: (usize, usize)) -> Footprint { Footprint::rectangle(size) }
+
+    #[staticmethod]
+    #[name = "named"]
+    fn named_py(name: &str) -> Option<Footprint> { Footprint::named(name) }
+
+    #[staticmethod]
+    fn names() -> Vec<&'static str> { FOOTPRINT_NAMES.to_vec() }
+
+    #[getter(corner)]
+    fn get_corner(&self) -> (f32, f32) { self.corner }
+
+    #[getter(pathing)]
+    fn get_pathing(&self) -> Vec<(usize, usize)> { self.pathing.clone() }
+
+    #[getter(placement)]
+    fn get_placement(&self) -> Vec<(usize, usize)> { self.placement.clone() }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PathFind {
+    /// Blocks the pathing cells of a shape, either a name from `Footprint.names()` or a `Footprint`.
+    #[name = "create_footprint_block"]
+    fn create_footprint_block_py(&mut self, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.create_footprint_block(&extract_footprint(shape)?, center);
+        Ok(())
+    }
+
+    #[name = "remove_footprint_block"]
+    fn remove_footprint_block_py(&mut self, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.remove_footprint_block(&extract_footprint(shape)?, center);
+        Ok(())
+    }
+}
@@ -1,13 +1,26 @@
+use crate::binary::{invalid_data, BinaryReader, BinaryWriter};
+use crate::error::{Error, Result};
+use crate::mapping::cache::CACHE_VERSION;
+#[cfg(feature = "python")]
+use crate::numpy_grid::{extract_grid, grid_to_array, Orientation};
+#[cfg(feature = "python")]
+use numpy::PyArray2;
 use pathfinding::prelude::{absdiff, astar, dijkstra_all, dijkstra_partial};
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+use std::io;
 
 mod angles;
+pub mod footprint;
+pub mod obstacles;
 pub mod pos;
 mod pos_large;
 pub mod rectangle;
 mod search_grid;
 
-#[pyclass]
+const MAGIC: &[u8; 8] = b"SC2PPATH";
+
+#[cfg_attr(feature = "python", pyclass)]
 pub struct PathFind {
     pub map: Vec<Vec<usize>>,
     original_map: Vec<Vec<usize>>,
@@ -16,6 +29,7 @@ pub struct PathFind {
     normal_influence: usize,
     auto_correct: bool,
     free_finder: search_grid::FreeFinder,
+    obstacles: obstacles::ObstacleLayer,
 }
 
 #[inline]
@@ -56,6 +70,12 @@ impl PathFind {
 }
 
 impl PathFind {
+    /// Creates the pathfinder from a `map[x][y]` grid where 0 is blocked and positive values are pathable.
+    pub fn new(map: Vec<Vec<usize>>) -> Result<Self> {
+        validate_grid(&map)?;
+        Ok(PathFind::new_internal(map))
+    }
+
     pub fn new_internal(map: Vec<Vec<usize>>) -> Self {
         let width = map.len();
         let original_map = map.clone();
@@ -63,6 +83,7 @@ impl PathFind {
         let normal_influence: usize = 1;
         let auto_correct: bool = true;
         let free_finder = search_grid::FreeFinder::new();
+        let obstacles = obstacles::ObstacleLayer::new(width, height);
 
         PathFind { map,
                    original_map,
@@ -70,98 +91,107 @@ impl PathFind {
                    height,
                    normal_influence,
                    auto_correct,
-                   free_finder }
+                   free_finder,
+                   obstacles }
     }
-    // Removes multiple blocks on the grid and makes it pathable
-    // center = center of block
-    pub fn remove_blocks_rust(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
-        for center in centers {
-            let rect = rectangle::Rectangle::init_from_center(*center, size, self.width, self.height);
 
-            for x in rect.x..rect.x_end {
-                for y in rect.y..rect.y_end {
-                    self.map[x][y] = self.normal_influence;
-                }
-            }
-        }
+    /// Writes current and original grids along with the influence settings and obstacles.
+    pub(crate) fn write_binary(&self, writer: &mut BinaryWriter) {
+        writer.write_grid(&self.map);
+        writer.write_grid(&self.original_map);
+        writer.write_usize(self.normal_influence);
+        writer.write_bool(self.auto_correct);
+        self.obstacles.write_binary(writer);
     }
 
-    // Creates a block on the grid that is not pathable
-    // center = center of building
-    pub fn create_blocks_rust(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
-        for center in centers {
-            let rect = rectangle::Rectangle::init_from_center(*center, size, self.width, self.height);
-
-            for x in rect.x..rect.x_end {
-                for y in rect.y..rect.y_end {
-                    self.map[x][y] = 0;
-                }
-            }
+    pub(crate) fn read_binary(reader: &mut BinaryReader) -> io::Result<Self> {
+        let map = reader.read_grid()?;
+        let original_map = reader.read_grid()?;
+        let normal_influence = reader.read_usize()?;
+        let auto_correct = reader.read_bool()?;
+
+        if map.is_empty()
+           || map[0].is_empty()
+           || map.len() != original_map.len()
+           || map[0].len() != original_map[0].len()
+        {
+            return Err(invalid_data("pathing grid sizes do not match"));
         }
-    }
-}
 
-#[pymethods]
-impl PathFind {
-    #[new]
-    fn new(map: Vec<Vec<usize>>) -> Self {
         let width = map.len();
-        let original_map = map.clone();
         let height = map[0].len();
-        let normal_influence: usize = 1;
-        let auto_correct: bool = true;
         let free_finder = search_grid::FreeFinder::new();
+        let obstacles = obstacles::ObstacleLayer::read_binary(reader, width, height)?;
+
+        Ok(PathFind { map,
+                      original_map,
+                      width,
+                      height,
+                      normal_influence,
+                      auto_correct,
+                      free_finder,
+                      obstacles })
+    }
 
-        PathFind { map,
-                   original_map,
-                   width,
-                   height,
-                   normal_influence,
-                   auto_correct,
-                   free_finder }
+    /// Binary encoding of the grids and influence settings, used for pickling.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = BinaryWriter::new();
+        writer.write_bytes(MAGIC);
+        writer.write_u32(CACHE_VERSION);
+        self.write_binary(&mut writer);
+        writer.buffer
     }
 
-    // object.width
-    #[getter(width)]
-    fn get_width(&self) -> PyResult<usize> { Ok(self.width) }
+    pub fn from_bytes(data: &[u8]) -> io::Result<PathFind> {
+        let mut reader = BinaryReader::new(data);
 
-    // object.height
-    #[getter(height)]
-    fn get_height(&self) -> PyResult<usize> { Ok(self.height) }
+        if reader.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(invalid_data("not pathfinding data"));
+        }
 
-    // object.normal_influence
-    #[getter(normal_influence)]
-    fn get_normal_influence(&self) -> PyResult<usize> { Ok(self.normal_influence) }
+        let version = reader.read_u32()?;
+        if version != CACHE_VERSION {
+            return Err(invalid_data(&format!("unsupported pathfinding data version {}", version)));
+        }
 
-    // object.map
-    #[getter(map)]
-    fn get_map(&self) -> PyResult<Vec<Vec<usize>>> { Ok(self.map.clone()) }
+        let path_find = PathFind::read_binary(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(invalid_data("trailing data in pathfinding data"));
+        }
 
-    // object.map(2dArray)
-    #[setter(map)]
-    fn set_map(&mut self, value: Vec<Vec<usize>>) -> PyResult<()> {
-        self.map = value;
-        Ok(())
+        Ok(path_find)
     }
+}
 
-    // object.auto_correct
-    #[getter(auto_correct)]
-    fn get_auto_correct(&self) -> PyResult<bool> { Ok(self.auto_correct) }
+impl PathFind {
+    pub fn normal_influence(&self) -> usize { self.normal_influence }
 
-    // object.map(bool)
-    #[setter(auto_correct)]
-    fn set_auto_correct(&mut self, value: bool) -> PyResult<()> {
-        self.auto_correct = value;
+    /// When disabled, unpathable start and end points are moved to the closest pathable point.
+    pub fn auto_correct(&self) -> bool { self.auto_correct }
+
+    pub fn set_auto_correct(&mut self, value: bool) { self.auto_correct = value; }
+
+    /// Replaces the current grid, must have the same size as the original grid.
+    pub fn set_map(&mut self, map: Vec<Vec<usize>>) -> Result<()> {
+        validate_grid(&map)?;
+        if map.len() != self.width || map[0].len() != self.height {
+            return Err(Error::GridSizeMismatch { expected: (self.width, self.height),
+                                                 found: (map.len(), map[0].len()) });
+        }
+
+        self.map = map;
         Ok(())
     }
 
-    pub fn reset(&mut self) -> PyResult<()> {
+    /// Original grid the pathfinder was created with.
+    pub fn original_map(&self) -> &[Vec<usize>] { &self.original_map }
+
+    /// Restores the original grid, obstacles stay blocked.
+    pub fn reset(&mut self) {
         self.map = self.original_map.clone();
-        Ok(())
+        self.apply_obstacles();
     }
 
-    pub fn reset_void(&mut self) { self.map = self.original_map.clone(); }
-
     // Creates a block on the grid that is not pathable
     // center = center of building
     pub fn create_block(&mut self, center: (f32, f32), size: (usize, usize)) {
@@ -176,9 +206,9 @@ impl PathFind {
 
     // Creates a block on the grid that is not pathable
     // center = center of building
-    pub fn create_blocks(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
+    pub fn create_blocks(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
         for center in centers {
-            let rect = rectangle::Rectangle::init_from_center(center, size, self.width, self.height);
+            let rect = rectangle::Rectangle::init_from_center(*center, size, self.width, self.height);
 
             for x in rect.x..rect.x_end {
                 for y in rect.y..rect.y_end {
@@ -188,18 +218,39 @@ impl PathFind {
         }
     }
 
-    // Removes a block on the grid and makes it pathable
+    // Removes a block on the grid and makes it pathable where the original grid was, except under obstacles
     // center = center of block
     pub fn remove_block(&mut self, center: (f32, f32), size: (usize, usize)) {
         let rect = rectangle::Rectangle::init_from_center(center, size, self.width, self.height);
 
         for x in rect.x..rect.x_end {
             for y in rect.y..rect.y_end {
-                self.map[x][y] = self.normal_influence;
+                self.restore_cell(x, y);
             }
         }
     }
 
+    // Removes multiple blocks on the grid and makes it pathable
+    // center = center of block
+    pub fn remove_blocks(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
+        for center in centers {
+            self.remove_block(*center, size);
+        }
+    }
+
+    #[deprecated(since = "0.3.1", note = "use `create_blocks`")]
+    pub fn create_blocks_rust(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
+        self.create_blocks(centers, size)
+    }
+
+    #[deprecated(since = "0.3.1", note = "use `remove_blocks`")]
+    pub fn remove_blocks_rust(&mut self, centers: &[(f32, f32)], size: (usize, usize)) {
+        self.remove_blocks(centers, size)
+    }
+
+    #[deprecated(since = "0.3.1", note = "use `reset`")]
+    pub fn reset_void(&mut self) { self.reset() }
+
     pub fn normalize_influence(&mut self, value: usize) {
         self.normal_influence = value;
 
@@ -213,12 +264,13 @@ impl PathFind {
     }
 
     /// Adds influence based on euclidean distance
-    pub fn add_influence(&mut self, positions: Vec<(usize, usize)>, max: f32, distance: f32) -> PyResult<()> {
+    pub fn add_influence(&mut self, positions: &[(usize, usize)], max: f32, distance: f32) {
         let mult = 1.0 / (distance * pos::MULTF32);
         let diameter = ((distance * 2f32) as usize) + 2;
         let rect_size = (diameter, diameter);
 
         for position in positions {
+            let position = *position;
             let rect = rectangle::Rectangle::init_from_center2(position, rect_size, self.width, self.height);
 
             for x in rect.x..rect.x_end {
@@ -230,12 +282,10 @@ impl PathFind {
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Adds influence based on euclidean distance
-    pub fn add_influence_flat(&mut self, positions: Vec<(usize, usize)>, max: f32, distance: f32) -> PyResult<()> {
+    pub fn add_influence_flat(&mut self, positions: &[(usize, usize)], max: f32, distance: f32) {
         let value = max as usize;
         let mult_distance = distance * pos::MULTF32;
 
@@ -243,6 +293,7 @@ impl PathFind {
         let rect_size = (diameter, diameter);
 
         for position in positions {
+            let position = *position;
             let rect = rectangle::Rectangle::init_from_center2(position, rect_size, self.width, self.height);
 
             for x in rect.x..rect.x_end {
@@ -253,16 +304,14 @@ impl PathFind {
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Adds influence based on walk distance
-    pub fn add_walk_influence(&mut self, positions: Vec<(usize, usize)>, max: f32, distance: f32) -> PyResult<()> {
+    pub fn add_walk_influence(&mut self, positions: &[(usize, usize)], max: f32, distance: f32) {
         let mult = 1.0 / distance;
         let max_int = max as usize;
 
-        for position in &positions {
+        for position in positions {
             if self.map[position.0][position.1] == 0 {
                 continue;
             }
@@ -280,15 +329,13 @@ impl PathFind {
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Adds influence based on walk distance
-    pub fn add_walk_influence_flat(&mut self, positions: Vec<(usize, usize)>, max: f32, distance: f32) {
+    pub fn add_walk_influence_flat(&mut self, positions: &[(usize, usize)], max: f32, distance: f32) {
         let max_int = max as usize;
 
-        for position in &positions {
+        for position in positions {
             let corrected_position = self.get_closest_pathable(*position);
 
             if self.map[corrected_position.0][corrected_position.1] == 0 {
@@ -413,6 +460,42 @@ impl PathFind {
         (path, distance)
     }
 
+    /// Finds the fastest path with a speed multiplier for every cell, returns the path and its time
+    /// as the distance covered at speed 1.
+    pub fn find_path_speed(&self,
+                           start: (usize, usize),
+                           end: (usize, usize),
+                           speed: &[Vec<f32>])
+                           -> (Vec<(usize, usize)>, f32) {
+        let corrected_start = self.get_closest_pathable(start);
+        let corrected_end = self.get_closest_pathable(end);
+
+        let start: pos::Pos = pos::Pos(corrected_start.0, corrected_start.1);
+        let goal: pos::Pos = pos::Pos(corrected_end.0, corrected_end.1);
+        let grid: &Vec<Vec<usize>> = &self.map;
+        // Heuristic for the fastest cells keeps it admissible
+        let max_speed = speed.iter().flatten().cloned().fold(1.0, f32::max);
+
+        let result = astar(&start,
+                           |p| {
+                               p.successors(grid)
+                                .into_iter()
+                                .filter(|(next, _)| speed[next.0][next.1] > 0.0)
+                                .map(|(next, cost)| {
+                                    let time = (cost as f32 / speed[next.0][next.1]) as usize;
+                                    (next, time)
+                                })
+                                .collect::<Vec<_>>()
+                           },
+                           |p| (p.octile_distance(&goal) as f32 / max_speed) as usize,
+                           |p| *p == goal);
+
+        match result {
+            None => (Vec::new(), 0.0),
+            Some((path, time)) => (path.into_iter().map(|pos| (pos.0, pos.1)).collect(), time as f32 / pos::MULTF32),
+        }
+    }
+
     /// Find the shortest path values without considering influence and returns the path and distance
     pub fn find_path_large(&self,
                            start: (usize, usize),
@@ -547,7 +630,7 @@ impl PathFind {
     }
 
     /// Finds all reachable destinations from selected start point. Ignores influence.
-    pub fn find_all_destinations(&self, start: (usize, usize)) -> PyResult<Vec<((usize, usize), f32)>> {
+    pub fn find_all_destinations(&self, start: (usize, usize)) -> Vec<((usize, usize), f32)> {
         let start: pos::Pos = pos::Pos(start.0, start.1);
         let grid: &Vec<Vec<usize>> = &self.map;
         let result = dijkstra_all(&start, |p| p.successors(&grid));
@@ -562,12 +645,12 @@ impl PathFind {
             destination_collection.push(((x, y), d));
         }
 
-        Ok(destination_collection)
+        destination_collection
     }
 
     /// Finds all reachable destinations from selected start point. Ignores influence.
-    pub fn find_destinations_in(&self, start: (usize, usize), distance: f32) -> PyResult<Vec<((usize, usize), f32)>> {
-        Ok(self.find_destinations_in_inline(start, distance))
+    pub fn find_destinations_in(&self, start: (usize, usize), distance: f32) -> Vec<((usize, usize), f32)> {
+        self.find_destinations_in_inline(start, distance)
     }
 
     #[inline]
@@ -620,7 +703,7 @@ impl PathFind {
     }
 
     #[inline]
-    fn get_closest_pathable(&self, start: (usize, usize)) -> (usize, usize) {
+    pub(crate) fn get_closest_pathable(&self, start: (usize, usize)) -> (usize, usize) {
         if self.auto_correct || self.map[start.0][start.1] > 0 {
             start
         } else {
@@ -743,3 +826,205 @@ impl PathFind {
         return destination_collection;
     }
 }
+
+fn validate_grid(map: &[Vec<usize>]) -> Result<()> {
+    if map.is_empty() || map[0].is_empty() {
+        return Err(Error::EmptyGrid);
+    }
+
+    let height = map[0].len();
+    if let Some(column) = map.iter().find(|column| column.len() != height) {
+        return Err(Error::GridSizeMismatch { expected: (map.len(), height),
+                                             found: (map.len(), column.len()) });
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PathFind {
+    /// Accepts a numpy array or nested lists, `orientation` is either "xy" for `map[x][y]` or "yx" for `map[y][x]`.
+    #[new]
+    #[args(orientation = "\"xy\"")]
+    fn new_py(map: &PyAny, orientation: &str) -> PyResult<Self> {
+        let map = extract_grid(map, Orientation::parse(orientation)?)?;
+        Ok(PathFind::new_internal(map))
+    }
+
+    // object.width
+    #[getter(width)]
+    fn get_width(&self) -> PyResult<usize> { Ok(self.width) }
+
+    // object.height
+    #[getter(height)]
+    fn get_height(&self) -> PyResult<usize> { Ok(self.height) }
+
+    // object.normal_influence
+    #[getter(normal_influence)]
+    fn get_normal_influence(&self) -> PyResult<usize> { Ok(self.normal_influence) }
+
+    // object.map, a read-only copy
+    #[getter(map)]
+    fn get_map<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.map, Orientation::XY)
+    }
+
+    // object.map(2dArray)
+    #[setter(map)]
+    fn set_map_py(&mut self, value: &PyAny) -> PyResult<()> {
+        Ok(self.set_map(extract_grid(value, Orientation::XY)?)?)
+    }
+
+    /// Read-only copy of the current grid as numpy array in the requested orientation.
+    #[args(orientation = "\"xy\"")]
+    fn map_array<'py>(&self, py: Python<'py>, orientation: &str) -> PyResult<&'py PyArray2<usize>> {
+        grid_to_array(py, &self.map, Orientation::parse(orientation)?)
+    }
+
+    // object.auto_correct
+    #[getter(auto_correct)]
+    fn get_auto_correct(&self) -> PyResult<bool> { Ok(self.auto_correct) }
+
+    // object.map(bool)
+    #[setter(auto_correct)]
+    fn set_auto_correct_py(&mut self, value: bool) -> PyResult<()> {
+        self.auto_correct = value;
+        Ok(())
+    }
+
+    #[name = "reset"]
+    fn reset_py(&mut self) { self.reset(); }
+
+    #[name = "create_block"]
+    fn create_block_py(&mut self, center: (f32, f32), size: (usize, usize)) { self.create_block(center, size); }
+
+    #[name = "create_blocks"]
+    fn create_blocks_py(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
+        self.create_blocks(&centers, size);
+    }
+
+    #[name = "remove_block"]
+    fn remove_block_py(&mut self, center: (f32, f32), size: (usize, usize)) { self.remove_block(center, size); }
+
+    #[name = "remove_blocks"]
+    fn remove_blocks_py(&mut self, centers: Vec<(f32, f32)>, size: (usize, usize)) {
+        self.remove_blocks(&centers, size);
+    }
+
+    #[name = "normalize_influence"]
+    fn normalize_influence_py(&mut self, value: usize) { self.normalize_influence(value); }
+
+    /// Adds influence based on euclidean distance
+    #[name = "add_influence"]
+    fn add_influence_py(&mut self, py: Python, positions: Vec<(usize, usize)>, max: f32, distance: f32) {
+        py.allow_threads(|| self.add_influence(&positions, max, distance));
+    }
+
+    /// Adds influence based on euclidean distance
+    #[name = "add_influence_flat"]
+    fn add_influence_flat_py(&mut self, py: Python, positions: Vec<(usize, usize)>, max: f32, distance: f32) {
+        py.allow_threads(|| self.add_influence_flat(&positions, max, distance));
+    }
+
+    /// Adds influence based on walk distance
+    #[name = "add_walk_influence"]
+    fn add_walk_influence_py(&mut self, py: Python, positions: Vec<(usize, usize)>, max: f32, distance: f32) {
+        py.allow_threads(|| self.add_walk_influence(&positions, max, distance));
+    }
+
+    /// Adds influence based on walk distance
+    #[name = "add_walk_influence_flat"]
+    fn add_walk_influence_flat_py(&mut self, py: Python, positions: Vec<(usize, usize)>, max: f32, distance: f32) {
+        py.allow_threads(|| self.add_walk_influence_flat(&positions, max, distance));
+    }
+
+    /// Finds the first reachable position within specified walking distance from the center point with lowest value
+    #[name = "lowest_influence_walk"]
+    fn lowest_influence_walk_py(&self, py: Python, center: (usize, usize), distance: f32) -> ((usize, usize), f32) {
+        py.allow_threads(|| self.lowest_influence_walk(center, distance))
+    }
+
+    /// Finds the first reachable position within specified distance from the center point with lowest value
+    #[name = "lowest_influence"]
+    fn lowest_influence_py(&self, py: Python, center: (f32, f32), distance: usize) -> ((usize, usize), f32) {
+        py.allow_threads(|| self.lowest_influence(center, distance))
+    }
+
+    /// Find the shortest path values without considering influence and returns the path and distance
+    #[name = "find_path"]
+    fn find_path_py(&self,
+                    py: Python,
+                    start: (usize, usize),
+                    end: (usize, usize),
+                    possible_heuristic: Option<u8>)
+                    -> (Vec<(usize, usize)>, f32) {
+        py.allow_threads(|| self.find_path(start, end, possible_heuristic))
+    }
+
+    /// Find the shortest path values without considering influence and returns the path and distance
+    #[name = "find_path_large"]
+    fn find_path_large_py(&self,
+                          py: Python,
+                          start: (usize, usize),
+                          end: (usize, usize),
+                          possible_heuristic: Option<u8>)
+                          -> (Vec<(usize, usize)>, f32) {
+        py.allow_threads(|| self.find_path_large(start, end, possible_heuristic))
+    }
+
+    /// Find the path using influence values and returns the path and distance
+    #[name = "find_path_influence"]
+    fn find_path_influence_py(&self,
+                              py: Python,
+                              start: (usize, usize),
+                              end: (usize, usize),
+                              possible_heuristic: Option<u8>)
+                              -> (Vec<(usize, usize)>, f32) {
+        py.allow_threads(|| self.find_path_influence(start, end, possible_heuristic))
+    }
+
+    /// Find the path using influence values and returns the path and distance
+    #[name = "find_path_influence_large"]
+    fn find_path_influence_large_py(&self,
+                                    py: Python,
+                                    start: (usize, usize),
+                                    end: (usize, usize),
+                                    possible_heuristic: Option<u8>)
+                                    -> (Vec<(usize, usize)>, f32) {
+        py.allow_threads(|| self.find_path_influence_large(start, end, possible_heuristic))
+    }
+
+    /// Finds all reachable destinations from selected start point. Ignores influence.
+    #[name = "find_all_destinations"]
+    fn find_all_destinations_py(&self, py: Python, start: (usize, usize)) -> Vec<((usize, usize), f32)> {
+        py.allow_threads(|| self.find_all_destinations(start))
+    }
+
+    /// Finds all reachable destinations from selected start point. Ignores influence.
+    #[name = "find_destinations_in"]
+    fn find_destinations_in_py(&self, py: Python, start: (usize, usize), distance: f32) -> Vec<((usize, usize), f32)> {
+        py.allow_threads(|| self.find_destinations_in(start, distance))
+    }
+
+    /// Finds a compromise where low influence matches with close position to the start position.
+    #[name = "find_low_inside_walk"]
+    fn find_low_inside_walk_py(&self,
+                               py: Python,
+                               start: (f32, f32),
+                               target: (f32, f32),
+                               distance: f32)
+                               -> ((f32, f32), f32) {
+        py.allow_threads(|| self.find_low_inside_walk(start, target, distance))
+    }
+
+    #[name = "invert_djiktra"]
+    fn invert_djiktra_py(&self, py: Python, start: (f32, f32), distance: f32) -> Vec<((usize, usize), f32)> {
+        py.allow_threads(|| self.invert_djiktra(start, distance))
+    }
+
+    #[name = "djiktra"]
+    fn djiktra_py(&self, py: Python, start: (f32, f32), distance: f32) -> Vec<((usize, usize), f32)> {
+        py.allow_threads(|| self.djiktra(start, distance))
+    }
+}
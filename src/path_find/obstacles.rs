@@ -0,0 +1,191 @@
+use crate::binary::{BinaryReader, BinaryWriter};
+use crate::path_find::footprint::Footprint;
+#[cfg(feature = "python")]
+use crate::path_find::footprint::extract_footprint;
+use crate::path_find::PathFind;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+use std::io;
+
+/// Structure blocking the grid, tracked by id so that overlapping obstacles can be removed in any order.
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Obstacle {
+    /// Usually the tag of the unit
+    pub id: u64,
+    pub footprint: Footprint,
+    pub center: (f32, f32),
+}
+
+impl Obstacle {
+    /// Grid cells where the obstacle blocks pathing.
+    pub fn pathing_cells(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
+        self.footprint.cells(&self.footprint.pathing, self.center, width, height)
+    }
+
+    /// Grid cells where the obstacle blocks placement.
+    pub fn placement_cells(&self, width: usize, height: usize) -> Vec<(usize, usize)> {
+        self.footprint.cells(&self.footprint.placement, self.center, width, height)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Obstacle {
+    #[getter(id)]
+    fn get_id(&self) -> u64 { self.id }
+
+    #[getter(footprint)]
+    fn get_footprint(&self) -> Footprint { self.footprint.clone() }
+
+    #[getter(center)]
+    fn get_center(&self) -> (f32, f32) { self.center }
+}
+
+/// Obstacles of a grid with the number of obstacles blocking pathing and placement on each cell.
+#[derive(Clone, Debug)]
+pub(crate) struct ObstacleLayer {
+    obstacles: BTreeMap<u64, Obstacle>,
+    cover: Vec<Vec<usize>>,
+    placement_cover: Vec<Vec<usize>>,
+}
+
+impl ObstacleLayer {
+    pub fn new(width: usize, height: usize) -> Self {
+        ObstacleLayer { obstacles: BTreeMap::new(),
+                        cover: vec![vec![0; height]; width],
+                        placement_cover: vec![vec![0; height]; width] }
+    }
+
+    pub(crate) fn write_binary(&self, writer: &mut BinaryWriter) {
+        writer.write_usize(self.obstacles.len());
+        for obstacle in self.obstacles.values() {
+            writer.write_u64(obstacle.id);
+            writer.write_point_f32(obstacle.center);
+            writer.write_point_f32(obstacle.footprint.corner);
+            writer.write_points(&obstacle.footprint.pathing);
+            writer.write_points(&obstacle.footprint.placement);
+        }
+    }
+
+    pub(crate) fn read_binary(reader: &mut BinaryReader, width: usize, height: usize) -> io::Result<Self> {
+        let mut layer = ObstacleLayer::new(width, height);
+        let count = reader.read_len(8)?;
+        for _ in 0..count {
+            let id = reader.read_u64()?;
+            let center = reader.read_point_f32()?;
+            let corner = reader.read_point_f32()?;
+            let footprint = Footprint::new(corner, reader.read_points()?, reader.read_points()?);
+            layer.add(Obstacle { id, footprint, center });
+        }
+
+        Ok(layer)
+    }
+
+    /// Adds the obstacle and returns the cells it covers, an obstacle with the same id must be removed first.
+    fn add(&mut self, obstacle: Obstacle) -> Vec<(usize, usize)> {
+        let (width, height) = (self.cover.len(), self.cover[0].len());
+        let cells = obstacle.pathing_cells(width, height);
+        for &(x, y) in &cells {
+            self.cover[x][y] += 1;
+        }
+        for (x, y) in obstacle.placement_cells(width, height) {
+            self.placement_cover[x][y] += 1;
+        }
+
+        self.obstacles.insert(obstacle.id, obstacle);
+        cells
+    }
+
+    /// Removes the obstacle and returns it with the cells no other obstacle covers.
+    fn remove(&mut self, id: u64) -> Option<(Obstacle, Vec<(usize, usize)>)> {
+        let obstacle = self.obstacles.remove(&id)?;
+        let (width, height) = (self.cover.len(), self.cover[0].len());
+        let mut freed = Vec::new();
+        for (x, y) in obstacle.pathing_cells(width, height) {
+            self.cover[x][y] -= 1;
+            if self.cover[x][y] == 0 {
+                freed.push((x, y));
+            }
+        }
+        for (x, y) in obstacle.placement_cells(width, height) {
+            self.placement_cover[x][y] -= 1;
+        }
+
+        Some((obstacle, freed))
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PathFind {
+    /// Blocks the pathing cells of a shape under the id, replacing an earlier obstacle with the same id.
+    #[name = "add_obstacle"]
+    fn add_obstacle_py(&mut self, id: u64, shape: &PyAny, center: (f32, f32)) -> PyResult<()> {
+        self.add_obstacle(id, extract_footprint(shape)?, center);
+        Ok(())
+    }
+
+    /// Removes the obstacle, returns false when there was none with the id.
+    #[name = "remove_obstacle"]
+    fn remove_obstacle_py(&mut self, id: u64) -> bool { self.remove_obstacle(id).is_some() }
+
+    #[getter(obstacles)]
+    fn get_obstacles(&self) -> Vec<Obstacle> { self.obstacles().cloned().collect() }
+}
+
+impl PathFind {
+    /// Blocks the pathing cells of the footprint until the obstacle is removed.
+    ///
+    /// An obstacle already added with the same id is removed first, so moving obstacles can simply be added again.
+    pub fn add_obstacle(&mut self, id: u64, footprint: Footprint, center: (f32, f32)) {
+        self.remove_obstacle(id);
+        for (x, y) in self.obstacles.add(Obstacle { id, footprint, center }) {
+            self.map[x][y] = 0;
+        }
+    }
+
+    /// Removes the obstacle with the id and returns it.
+    ///
+    /// Cells covered by other obstacles stay blocked, other cells get their pathing back from the original grid.
+    pub fn remove_obstacle(&mut self, id: u64) -> Option<Obstacle> {
+        let (obstacle, freed) = self.obstacles.remove(id)?;
+        for (x, y) in freed {
+            self.restore_cell(x, y);
+        }
+
+        Some(obstacle)
+    }
+
+    /// Gives the cell its pathing back from the original grid, unless an obstacle still covers it.
+    pub(crate) fn restore_cell(&mut self, x: usize, y: usize) {
+        if self.obstacles.cover[x][y] == 0 {
+            self.map[x][y] = if self.original_map[x][y] > 0 { self.normal_influence } else { 0 };
+        }
+    }
+
+    /// Current obstacles in the order of their ids.
+    pub fn obstacles(&self) -> impl Iterator<Item = &Obstacle> { self.obstacles.obstacles.values() }
+
+    /// Whether any obstacle blocks pathing on the cell.
+    pub fn is_obstacle(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.width && pos.1 < self.height && self.obstacles.cover[pos.0][pos.1] > 0
+    }
+
+    /// Whether any obstacle blocks placement on the cell.
+    pub fn is_placement_obstacle(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.width && pos.1 < self.height && self.obstacles.placement_cover[pos.0][pos.1] > 0
+    }
+
+    /// Blocks the cells of all obstacles again, after the grid was reset.
+    pub(crate) fn apply_obstacles(&mut self) {
+        for x in 0..self.width {
+            for y in 0..self.height {
+                if self.obstacles.cover[x][y] > 0 {
+                    self.map[x][y] = 0;
+                }
+            }
+        }
+    }
+}
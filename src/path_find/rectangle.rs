@@ -42,4 +42,12 @@ impl Rectangle {
                     x_end,
                     y_end }
     }
+
+    /// Number of free cells between the rectangles along the axis where they are furthest apart,
+    /// 0 when they touch and negative when they overlap.
+    pub fn gap(&self, other: &Rectangle) -> isize {
+        let x = (other.x as isize - self.x_end as isize).max(self.x as isize - other.x_end as isize);
+        let y = (other.y as isize - self.y_end as isize).max(self.y as isize - other.y_end as isize);
+        x.max(y)
+    }
 }
@@ -0,0 +1,66 @@
+//! Pickle support, `PathFind` and `Map` are restored from the binary cache encoding by module level functions.
+use crate::mapping::map::Map;
+use crate::path_find::PathFind;
+use pyo3::once_cell::GILOnceCell;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::wrap_pyfunction;
+
+static PATH_FIND_FROM_BYTES: GILOnceCell<PyObject> = GILOnceCell::new();
+static MAP_FROM_BYTES: GILOnceCell<PyObject> = GILOnceCell::new();
+
+#[pyfunction]
+#[name = "_path_find_from_bytes"]
+fn path_find_from_bytes(state: &PyBytes) -> PyResult<PathFind> { Ok(PathFind::from_bytes(state.as_bytes())?) }
+
+#[pyfunction]
+#[name = "_map_from_bytes"]
+fn map_from_bytes(state: &PyBytes) -> PyResult<Map> { Ok(Map::from_bytes(state.as_bytes())?) }
+
+/// Adds the restore functions to the module, pickle finds them through `__module__` and `__name__`.
+pub fn add_functions(py: Python, m: &PyModule) -> PyResult<()> {
+    for (function, cell) in &[(wrap_pyfunction!(path_find_from_bytes)(py), &PATH_FIND_FROM_BYTES),
+                              (wrap_pyfunction!(map_from_bytes)(py), &MAP_FROM_BYTES)]
+    {
+        function.as_ref(py).setattr("__module__", m.name()?)?;
+        m.add(function.getattr(py, "__name__")?.extract(py)?, function.clone_ref(py))?;
+        let _ = cell.set(py, function.clone_ref(py));
+    }
+
+    Ok(())
+}
+
+fn restore_function(py: Python, cell: &GILOnceCell<PyObject>) -> PyResult<PyObject> {
+    match cell.get(py) {
+        Some(function) => Ok(function.clone_ref(py)),
+        None => Err(pyo3::exceptions::RuntimeError::py_err("sc2pathlib module is not initialized")),
+    }
+}
+
+#[pymethods]
+impl PathFind {
+    fn __getstate__(&self, py: Python) -> PyObject { PyBytes::new(py, &self.to_bytes()).into() }
+
+    fn __setstate__(&mut self, state: &PyBytes) -> PyResult<()> {
+        *self = PathFind::from_bytes(state.as_bytes())?;
+        Ok(())
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<(PyObject, (PyObject,))> {
+        Ok((restore_function(py, &PATH_FIND_FROM_BYTES)?, (self.__getstate__(py),)))
+    }
+}
+
+#[pymethods]
+impl Map {
+    fn __getstate__(&self, py: Python) -> PyObject { PyBytes::new(py, &self.to_bytes()).into() }
+
+    fn __setstate__(&mut self, state: &PyBytes) -> PyResult<()> {
+        *self = Map::from_bytes(state.as_bytes())?;
+        Ok(())
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<(PyObject, (PyObject,))> {
+        Ok((restore_function(py, &MAP_FROM_BYTES)?, (self.__getstate__(py),)))
+    }
+}
@@ -0,0 +1,398 @@
+use crate::mapping::map::Map;
+#[cfg(feature = "python")]
+use crate::mapping::mappings::map_type_py;
+use crate::mapping::mappings::MapType;
+use crate::path_find::PathFind;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use std::fs;
+use std::io;
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [255, 255, 255];
+pub const PATH_COLOR: Color = [255, 0, 255];
+pub const OVERLORD_COLOR: Color = [0, 255, 255];
+pub const CHOKE_LINE_COLOR: Color = [255, 255, 0];
+
+/// Colors for the values returned by `Map::draw_climbs`
+const CLIMB_PALETTE: [Color; 7] = [[0, 0, 0],
+                                   [255, 128, 0],
+                                   [90, 90, 90],
+                                   [0, 128, 255],
+                                   [160, 0, 255],
+                                   [255, 0, 0],
+                                   [0, 200, 200]];
+
+/// RGB image with origin at the bottom left like the game coordinates.
+#[derive(Clone, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Image { width,
+                height,
+                pixels: vec![BLACK; width * height] }
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Color { self.pixels[(self.height - 1 - y) * self.width + x] }
+
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[(self.height - 1 - y) * self.width + x] = color;
+        }
+    }
+
+    pub fn draw_path(&mut self, path: &[(usize, usize)], color: Color) {
+        for point in path {
+            self.set(point.0, point.1, color);
+        }
+    }
+
+    pub fn draw_line(&mut self, start: (f32, f32), end: (f32, f32), color: Color) {
+        let dx = end.0 - start.0;
+        let dy = end.1 - start.1;
+        let steps = dx.abs().max(dy.abs()).ceil() as usize;
+
+        for i in 0..=steps {
+            let t = if steps == 0 { 0.0 } else { i as f32 / steps as f32 };
+            let x = start.0 + dx * t;
+            let y = start.1 + dy * t;
+            if x >= 0.0 && y >= 0.0 {
+                self.set(x as usize, y as usize, color);
+            }
+        }
+    }
+
+    /// Draws a small cross centered on the point.
+    pub fn draw_marker(&mut self, center: (f32, f32), color: Color) {
+        let x = center.0.round() as i64;
+        let y = center.1.round() as i64;
+
+        for offset in -1..=1 {
+            for point in &[(x + offset, y), (x, y + offset)] {
+                if point.0 >= 0 && point.1 >= 0 {
+                    self.set(point.0 as usize, point.1 as usize, color);
+                }
+            }
+        }
+    }
+
+    /// Nearest neighbour upscaling, equal to cv2 INTER_NEAREST resize.
+    pub fn scaled(&self, scale: usize) -> Image {
+        let scale = scale.max(1);
+        let mut result = Image::new(self.width * scale, self.height * scale);
+
+        for row in 0..result.height {
+            for column in 0..result.width {
+                result.pixels[row * result.width + column] = self.pixels[(row / scale) * self.width + column / scale];
+            }
+        }
+
+        result
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            data.extend_from_slice(pixel);
+        }
+        data
+    }
+
+    /// Grayscale output, uses the red channel.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut data = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for pixel in &self.pixels {
+            data.push(pixel[0]);
+        }
+        data
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in 0..self.height {
+            raw.push(0); // Filter type none
+            for pixel in &self.pixels[row * self.width..(row + 1) * self.width] {
+                raw.extend_from_slice(pixel);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bit depth, RGB, deflate, no filtering, no interlace
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut data = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        write_png_chunk(&mut data, b"IHDR", &header);
+        write_png_chunk(&mut data, b"IDAT", &zlib_stored(&raw));
+        write_png_chunk(&mut data, b"IEND", &[]);
+        data
+    }
+
+    /// Writes the image, format is picked from the file extension (png, ppm or pgm).
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let lower = path.to_lowercase();
+        let data = if lower.ends_with(".ppm") {
+            self.to_ppm()
+        } else if lower.ends_with(".pgm") {
+            self.to_pgm()
+        } else if lower.ends_with(".png") {
+            self.to_png()
+        } else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "image file must end with .png, .ppm or .pgm"));
+        };
+
+        fs::write(path, data)
+    }
+}
+
+fn write_png_chunk(data: &mut Vec<u8>, kind: &[u8; 4], content: &[u8]) {
+    data.extend_from_slice(&(content.len() as u32).to_be_bytes());
+    let start = data.len();
+    data.extend_from_slice(kind);
+    data.extend_from_slice(content);
+    let crc = crc32(&data[start..]);
+    data.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Zlib stream using uncompressed deflate blocks, keeps the encoder dependency free.
+fn zlib_stored(raw: &[u8]) -> Vec<u8> {
+    let mut data = vec![0x78, 0x01];
+    let mut chunks = raw.chunks(65535).peekable();
+
+    if chunks.peek().is_none() {
+        data.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+        data.push(last as u8);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(&(!len).to_le_bytes());
+        data.extend_from_slice(chunk);
+    }
+
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for byte in raw {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    data.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    data
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (!(crc & 1)).wrapping_add(1);
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Blue - green - yellow - red ramp for values between 0 and 1.
+pub fn color_ramp(value: f32) -> Color {
+    let stops: [Color; 4] = [[0, 0, 255], [0, 255, 0], [255, 255, 0], [255, 0, 0]];
+    let value = value.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (value as usize).min(stops.len() - 2);
+    let t = value - index as f32;
+    let from = stops[index];
+    let to = stops[index + 1];
+
+    [(from[0] as f32 + (to[0] as f32 - from[0] as f32) * t) as u8,
+     (from[1] as f32 + (to[1] as f32 - from[1] as f32) * t) as u8,
+     (from[2] as f32 + (to[2] as f32 - from[2] as f32) * t) as u8]
+}
+
+/// Pathable cells white, blocked cells black.
+pub fn grid_image(grid: &[Vec<usize>]) -> Image {
+    let mut image = Image::new(grid.len(), grid[0].len());
+
+    for (x, column) in grid.iter().enumerate() {
+        for (y, value) in column.iter().enumerate() {
+            if *value > 0 {
+                image.set(x, y, WHITE);
+            }
+        }
+    }
+
+    image
+}
+
+/// Blocked cells black, pathable cells colored from the lowest to the highest value of the grid.
+pub fn heatmap_image(grid: &[Vec<usize>]) -> Image {
+    let mut image = Image::new(grid.len(), grid[0].len());
+    let mut min = usize::MAX;
+    let mut max = 0;
+
+    for column in grid {
+        for value in column.iter().filter(|v| **v > 0) {
+            min = min.min(*value);
+            max = max.max(*value);
+        }
+    }
+
+    let range = if max > min { (max - min) as f32 } else { 1.0 };
+
+    for (x, column) in grid.iter().enumerate() {
+        for (y, value) in column.iter().enumerate() {
+            if *value > 0 {
+                image.set(x, y, color_ramp((*value - min) as f32 / range));
+            }
+        }
+    }
+
+    image
+}
+
+/// Value is used as an index to the palette, values outside the palette are drawn white.
+pub fn palette_image(grid: &[Vec<usize>], palette: &[Color]) -> Image {
+    let mut image = Image::new(grid.len(), grid[0].len());
+
+    for (x, column) in grid.iter().enumerate() {
+        for (y, value) in column.iter().enumerate() {
+            image.set(x, y, *palette.get(*value).unwrap_or(&WHITE));
+        }
+    }
+
+    image
+}
+
+/// Value is used directly as gray level.
+pub fn grayscale_image(grid: &[Vec<usize>]) -> Image {
+    let mut image = Image::new(grid.len(), grid[0].len());
+
+    for (x, column) in grid.iter().enumerate() {
+        for (y, value) in column.iter().enumerate() {
+            let gray = (*value).min(255) as u8;
+            image.set(x, y, [gray, gray, gray]);
+        }
+    }
+
+    image
+}
+
+impl PathFind {
+    /// Renders the current grid, as a heatmap when `influence` is set.
+    pub fn render(&self, influence: bool, paths: &[Vec<(usize, usize)>]) -> Image {
+        let mut image = if influence { heatmap_image(&self.map) } else { grid_image(&self.map) };
+
+        for path in paths {
+            image.draw_path(path, PATH_COLOR);
+        }
+
+        image
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl PathFind {
+    /// Saves the grid as png, ppm or pgm image, optionally with paths drawn on top.
+    #[args(paths = "Vec::new()", influence = "false", scale = "1")]
+    fn save_image(&self,
+                  file_name: &str,
+                  paths: Vec<Vec<(usize, usize)>>,
+                  influence: bool,
+                  scale: usize)
+                  -> PyResult<()> {
+        self.render(influence, &paths).scaled(scale).save(file_name)?;
+        Ok(())
+    }
+}
+
+impl Map {
+    pub fn render_layer(&self, map_type: MapType, influence: bool, paths: &[Vec<(usize, usize)>]) -> Image {
+        self.get_map(map_type).render(influence, paths)
+    }
+
+    /// Climb and cliff cells from `draw_climbs` with overlord spot markers.
+    pub fn render_climbs(&self) -> Image {
+        let mut image = palette_image(&self.draw_climbs(), &CLIMB_PALETTE);
+        self.draw_overlord_spots(&mut image);
+        image
+    }
+
+    /// Borders and chokes from `draw_chokes` with the choke main lines.
+    pub fn render_chokes(&self) -> Image {
+        let mut image = grayscale_image(&self.draw_chokes());
+
+        for choke in &self.chokes {
+            image.draw_line(choke.main_line.0, choke.main_line.1, CHOKE_LINE_COLOR);
+        }
+
+        image
+    }
+
+    /// Air pathing with overlord spots marked.
+    pub fn render_overlord_spots(&self) -> Image {
+        let mut image = grid_image(&self.air_pathing.map);
+        let ground = &self.ground_pathing.map;
+
+        for (x, column) in ground.iter().enumerate() {
+            for (y, value) in column.iter().enumerate() {
+                if *value > 0 {
+                    image.set(x, y, [128, 128, 128]);
+                }
+            }
+        }
+
+        self.draw_overlord_spots(&mut image);
+        image
+    }
+
+    fn draw_overlord_spots(&self, image: &mut Image) {
+        for spot in &self.overlord_spots {
+            image.draw_marker(*spot, OVERLORD_COLOR);
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl Map {
+    /// Saves the selected pathing map as an image, as a heatmap when `influence` is set.
+    #[args(paths = "Vec::new()", influence = "false", scale = "4")]
+    fn save_layer_image(&self,
+                        map_type: u8,
+                        file_name: &str,
+                        paths: Vec<Vec<(usize, usize)>>,
+                        influence: bool,
+                        scale: usize)
+                        -> PyResult<()> {
+        self.render_layer(map_type_py(map_type)?, influence, &paths).scaled(scale).save(file_name)?;
+        Ok(())
+    }
+
+    #[args(scale = "4")]
+    fn save_climbs_image(&self, file_name: &str, scale: usize) -> PyResult<()> {
+        self.render_climbs().scaled(scale).save(file_name)?;
+        Ok(())
+    }
+
+    #[args(scale = "4")]
+    fn save_chokes_image(&self, file_name: &str, scale: usize) -> PyResult<()> {
+        self.render_chokes().scaled(scale).save(file_name)?;
+        Ok(())
+    }
+
+    #[args(scale = "4")]
+    fn save_overlord_spots_image(&self, file_name: &str, scale: usize) -> PyResult<()> {
+        self.render_overlord_spots().scaled(scale).save(file_name)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+#include <math.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "sc2pathlib.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                        \
+    do {                                                                        \
+        if (!(condition)) {                                                     \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+            failures++;                                                         \
+        }                                                                       \
+    } while (0)
+
+static int near(float a, float b) { return fabsf(a - b) < 0.001f; }
+
+static void test_pathfind(void) {
+    size_t grid[10 * 10];
+    for (size_t i = 0; i < 10 * 10; i++) {
+        grid[i] = 1;
+    }
+
+    CHECK(sc2pathlib_pathfind_new(NULL, 10, 10) == NULL);
+    CHECK(sc2pathlib_pathfind_new(grid, 0, 10) == NULL);
+
+    PathFind *path_find = sc2pathlib_pathfind_new(grid, 10, 10);
+    CHECK(path_find != NULL);
+    CHECK(sc2pathlib_pathfind_width(path_find) == 10);
+    CHECK(sc2pathlib_pathfind_height(path_find) == 10);
+
+    Sc2Point start = {0, 0};
+    Sc2Point end = {9, 9};
+    Sc2Point path[64];
+    float distance = 0.0f;
+    size_t count = sc2pathlib_pathfind_find_path(path_find, start, end, -1, path, 64, &distance);
+    CHECK(count == 10);
+    CHECK(near(distance, 9.0f * 1.4142f));
+    CHECK(path[0].x == 0 && path[0].y == 0);
+    CHECK(path[count - 1].x == 9 && path[count - 1].y == 9);
+
+    // Too small buffer still reports the full length
+    Sc2Point short_path[4];
+    CHECK(sc2pathlib_pathfind_find_path(path_find, start, end, -1, short_path, 4, NULL) == count);
+
+    Sc2PointF center = {5.0f, 5.0f};
+    sc2pathlib_pathfind_create_block(path_find, center, 2, 2);
+    CHECK(sc2pathlib_pathfind_get(path_find, (Sc2Point){5, 5}) == 0);
+    float blocked_distance = 0.0f;
+    sc2pathlib_pathfind_find_path(path_find, start, end, -1, path, 64, &blocked_distance);
+    CHECK(blocked_distance > distance);
+
+    sc2pathlib_pathfind_reset(path_find);
+    CHECK(sc2pathlib_pathfind_get(path_find, (Sc2Point){5, 5}) == 1);
+
+    Sc2Point enemies[1] = {{5, 5}};
+    sc2pathlib_pathfind_add_influence(path_find, enemies, 1, 100.0f, 3.0f);
+    CHECK(sc2pathlib_pathfind_get(path_find, (Sc2Point){5, 5}) > 1);
+    float influence_distance = 0.0f;
+    count = sc2pathlib_pathfind_find_path_influence(path_find, start, end, -1, path, 64, &influence_distance);
+    CHECK(count > 0);
+    CHECK(influence_distance > distance);
+
+    sc2pathlib_pathfind_free(path_find);
+    sc2pathlib_pathfind_free(NULL);
+}
+
+#define MAP_SIZE 32
+
+static void test_map(void) {
+    uint8_t pathing[MAP_SIZE * MAP_SIZE];
+    uint8_t placement[MAP_SIZE * MAP_SIZE];
+    uint8_t heights[MAP_SIZE * MAP_SIZE];
+
+    for (size_t y = 0; y < MAP_SIZE; y++) {
+        for (size_t x = 0; x < MAP_SIZE; x++) {
+            size_t i = y * MAP_SIZE + x;
+            int inside = x >= 2 && x < MAP_SIZE - 2 && y >= 2 && y < MAP_SIZE - 2;
+            // Wall in the middle with a gap of four cells
+            int wall = (x == 15 || x == 16) && !(y >= 14 && y < 18);
+            // Unpathable plateau high above the ground
+            int plateau = x >= 5 && x < 9 && y >= 22 && y < 26;
+            pathing[i] = inside && !wall && !plateau;
+            placement[i] = 0;
+            heights[i] = plateau ? 60 : 20;
+        }
+    }
+
+    CHECK(sc2pathlib_map_new(pathing, placement, heights, MAP_SIZE, MAP_SIZE, 0, 0, 31, 31) == NULL);
+
+    Map *map = sc2pathlib_map_new(pathing, placement, heights, MAP_SIZE, MAP_SIZE, 1, 1, 30, 30);
+    CHECK(map != NULL);
+
+    Sc2PointF start = {5.0f, 5.0f};
+    Sc2PointF end = {25.0f, 5.0f};
+    Sc2Point path[256];
+    float ground_distance = 0.0f;
+    float air_distance = 0.0f;
+    size_t count = sc2pathlib_map_find_path(map, 0, start, end, -1, path, 256, &ground_distance);
+    CHECK(count > 0);
+    CHECK(sc2pathlib_map_find_path(map, 3, start, end, -1, path, 256, &air_distance) > 0);
+    CHECK(ground_distance > air_distance);
+    CHECK(sc2pathlib_map_find_path(map, 9, start, end, -1, path, 256, NULL) == 0);
+
+    PathFind *ground = sc2pathlib_map_pathing(map, 0);
+    CHECK(ground != NULL);
+    CHECK(sc2pathlib_pathfind_width(ground) == MAP_SIZE);
+    CHECK(sc2pathlib_pathfind_get(ground, (Sc2Point){15, 15}) == 1);
+
+    Sc2PointF gap = {16.0f, 16.0f};
+    sc2pathlib_map_create_block(map, gap, 4, 4);
+    CHECK(sc2pathlib_pathfind_get(ground, (Sc2Point){15, 15}) == 0);
+    sc2pathlib_map_remove_block(map, gap, 4, 4);
+    CHECK(sc2pathlib_pathfind_get(ground, (Sc2Point){15, 15}) == 1);
+
+    Sc2PointF enemies[1] = {{10.0f, 10.0f}};
+    sc2pathlib_map_add_influence_fading(map, 3, enemies, 1, 50.0f, 2.0f, 4.0f);
+    CHECK(sc2pathlib_pathfind_get(ground, (Sc2Point){10, 10}) > 1);
+    sc2pathlib_map_reset(map);
+    CHECK(sc2pathlib_pathfind_get(ground, (Sc2Point){10, 10}) == 1);
+
+    size_t spot_count = sc2pathlib_map_overlord_spots(map, NULL, 0);
+    CHECK(spot_count > 0);
+    Sc2PointF *spots = malloc(spot_count * sizeof(Sc2PointF));
+    CHECK(sc2pathlib_map_overlord_spots(map, spots, spot_count) == spot_count);
+    CHECK(spots[0].x >= 5.0f && spots[0].x < 9.0f && spots[0].y >= 22.0f && spots[0].y < 26.0f);
+    free(spots);
+
+    size_t choke_count = sc2pathlib_map_choke_count(map);
+    for (size_t i = 0; i < choke_count; i++) {
+        Sc2Choke choke;
+        CHECK(sc2pathlib_map_choke(map, i, &choke));
+        CHECK(choke.min_length > 0.0f);
+        Sc2Point *pixels = malloc((choke.pixel_count + 1) * sizeof(Sc2Point));
+        CHECK(sc2pathlib_map_choke_pixels(map, i, pixels, choke.pixel_count) == choke.pixel_count);
+        free(pixels);
+    }
+    Sc2Choke missing;
+    CHECK(!sc2pathlib_map_choke(map, choke_count, &missing));
+
+    sc2pathlib_map_free(map);
+}
+
+int main(void) {
+    test_pathfind();
+    test_map();
+
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+
+    printf("C API tests passed\n");
+    return 0;
+}
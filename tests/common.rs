@@ -1,7 +1,11 @@
+// Shared by the test crates, each of them only uses some of the helpers
+#![allow(dead_code)]
+
 use std::io::{BufReader, BufRead};
 use std::fs::File;
 use sc2pathlib::path_find;
 use sc2pathlib::mapping::map::Map;
+use sc2pathlib::npy;
 
 
 fn rot90(vec: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
@@ -46,6 +50,29 @@ pub fn get_choke_map() -> Map {
     let grid2 = read_vec_from_file("tests/choke.txt");
     let grid3 = read_vec_from_file("tests/choke.txt");
 
-    let map = Map::new(grid, grid2, grid3, 2, 2, 38, 38);
-    return map;
-}
\ No newline at end of file
+    Map::new(grid, grid2, grid3, 2, 2, 38, 38).unwrap()
+}
+
+/// Map created from the grids in `tests/<name>_*.npy`.
+pub fn get_npy_map(name: &str, x_start: usize, y_start: usize, x_end: usize, y_end: usize) -> Map {
+    let pathing = npy::load_grid(format!("tests/{}_pathing.npy", name)).unwrap();
+    let placement = npy::load_grid(format!("tests/{}_placement.npy", name)).unwrap();
+    let height = npy::load_grid(format!("tests/{}_height.npy", name)).unwrap();
+
+    Map::new(pathing, placement, height, x_start, y_start, x_end, y_end).unwrap()
+}
+
+pub fn get_automaton_map() -> Map { get_npy_map("AutomatonLE", 18, 16, 166, 164) }
+
+/// Square grid of the size with 1 inside the playable area, which leaves out 4 cells on every side.
+pub fn flat_grid(size: usize) -> Vec<Vec<usize>> {
+    let playable = 4..=size - 5;
+    (0..size).map(|x| (0..size).map(|y| (playable.contains(&x) && playable.contains(&y)) as usize).collect())
+             .collect()
+}
+
+/// Flat map of the size, pathable and placeable inside the playable area.
+pub fn flat_map(size: usize) -> Map {
+    let grid = flat_grid(size);
+    Map::new(grid.clone(), grid, vec![vec![10; size]; size], 4, 4, size - 5, size - 5).unwrap()
+}
@@ -0,0 +1,53 @@
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::mappings::MapType;
+mod common;
+
+#[test]
+fn test_colossus_cliffs() {
+    // Plateaus from left to right one and two levels up, separated by cliffs 2 cells wide
+    let height = |x: usize| if x <= 16 { 191 } else if x <= 26 { 207 } else { 239 };
+    let pathing: Vec<Vec<usize>> =
+        (0..40).map(|x| (0..40).map(|y| ((4..=35).contains(&y) && ![15, 16, 25, 26].contains(&x)) as usize).collect())
+               .collect();
+    let heights: Vec<Vec<usize>> = (0..40).map(|x| vec![height(x); 40]).collect();
+    let map = Map::new(pathing.clone(), pathing, heights, 4, 4, 35, 35).unwrap();
+    let colossus = &map.colossus_pathing.map;
+
+    assert_eq!(colossus[15][20], 1);
+    assert_eq!(colossus[16][20], 1);
+    assert_eq!(colossus[25][20], 0);
+    assert_eq!(colossus[26][20], 0);
+    // Outside of the playable area
+    assert_eq!(colossus[15][2], 0);
+
+    let (path, _) = map.find_path(MapType::Colossus, (10.0, 20.0), (20.0, 20.0), Some(1));
+    assert_eq!(path.last(), Some(&(20, 20)));
+    let (path, _) = map.find_path(MapType::Ground, (10.0, 20.0), (20.0, 20.0), Some(1));
+    assert!(path.is_empty());
+}
+
+#[test]
+fn test_colossus_map_real() {
+    let map = common::get_automaton_map();
+    let ground = map.ground_pathing.original_map();
+    let colossus = map.colossus_pathing.original_map();
+
+    for x in 0..map.points.len() {
+        for y in 0..map.points[0].len() {
+            if ground[x][y] > 0 {
+                assert!(colossus[x][y] > 0);
+            }
+            if !map.points[x][y].pathable {
+                assert!(colossus[x][y] == 0 || map.points[x][y].walkable);
+            }
+        }
+    }
+    assert_ne!(colossus, map.reaper_pathing.original_map());
+
+    // Colossi walk down the cliff of the main instead of taking the ramp
+    let main = (29.5, 65.5);
+    let natural = (35.0, 38.0);
+    let (_, ground_distance) = map.find_path(MapType::Ground, main, natural, Some(1));
+    let (_, colossus_distance) = map.find_path(MapType::Colossus, main, natural, Some(1));
+    assert!(colossus_distance < ground_distance);
+}
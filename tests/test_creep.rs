@@ -0,0 +1,48 @@
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::mappings::MapType;
+use sc2pathlib::mapping::PlacementOptions;
+mod common;
+
+/// Creep on the cells from `start` to `end`, both included
+fn creep_area(start: (usize, usize), end: (usize, usize)) -> Vec<Vec<usize>> {
+    (0..40).map(|x| (0..40).map(|y| (start.0 <= x && x <= end.0 && start.1 <= y && y <= end.1) as usize).collect())
+           .collect()
+}
+
+#[test]
+fn test_creep_placement() {
+    let mut map = common::flat_map(40);
+    assert!(map.set_creep(vec![vec![1; 20]; 40]).is_err());
+    map.set_creep(creep_area((10, 10), (19, 19))).unwrap();
+
+    assert!(map.has_creep((10, 19)));
+    assert!(!map.has_creep((20, 19)));
+    assert!(map.on_creep((3, 3), (18.5, 18.5)));
+    assert!(!map.on_creep((3, 3), (19.5, 18.5)));
+
+    let zerg = PlacementOptions { creep: true,
+                                  ..PlacementOptions::default() };
+    let position = map.find_placement_near((3, 3), (24.5, 14.5), 10.0, &zerg).unwrap();
+    assert_eq!(position, (18.5, 14.5));
+    let spots = map.find_placements((2, 2), (15.0, 15.0), 10.0, 100, &zerg);
+    assert_eq!(spots.len(), 25);
+
+    let restored = Map::from_bytes(&map.to_bytes()).unwrap();
+    assert_eq!(restored.creep, map.creep);
+}
+
+#[test]
+fn test_find_path_creep() {
+    let mut map = common::flat_map(40);
+    let (path, distance) = map.find_path(MapType::Ground, (6.0, 20.0), (34.0, 20.0), Some(0));
+    let (creep_path, time) = map.find_path_creep(MapType::Ground, (6.0, 20.0), (34.0, 20.0), 2.0);
+    assert_eq!(creep_path.len(), path.len());
+    assert_eq!(time, distance);
+
+    // A detour over creep is faster than the straight path
+    map.set_creep(creep_area((4, 14), (35, 16))).unwrap();
+    let (creep_path, time) = map.find_path_creep(MapType::Ground, (6.0, 20.0), (34.0, 20.0), 2.0);
+    assert!(creep_path.iter().any(|&(_, y)| y == 16));
+    assert!(time < distance);
+    assert!(time > 20.0);
+}
@@ -0,0 +1,78 @@
+use sc2pathlib::mapping::map::Map;
+mod common;
+
+/// Flat map placeable inside the playable area, resources are cut out of the grids like the game does.
+fn resource_map(minerals: &[(f32, f32)], geysers: &[(f32, f32)]) -> Map {
+    let mut pathing = vec![vec![1; 60]; 60];
+    for &(x, y) in minerals {
+        pathing[x as usize - 1][y as usize] = 0;
+        pathing[x as usize][y as usize] = 0;
+    }
+    for &(x, y) in geysers {
+        for column in pathing.iter_mut().skip(x as usize - 1).take(3) {
+            for cell in column.iter_mut().skip(y as usize - 1).take(3) {
+                *cell = 0;
+            }
+        }
+    }
+
+    let mut placement = pathing.clone();
+    for (x, column) in placement.iter_mut().enumerate() {
+        for (y, value) in column.iter_mut().enumerate() {
+            if x < 4 || y < 4 || x > 55 || y > 55 {
+                *value = 0;
+            }
+        }
+    }
+
+    let height = vec![vec![10; 60]; 60];
+    Map::new(pathing, placement, height, 4, 4, 55, 55).unwrap()
+}
+
+fn mineral_line(x: f32, y_start: usize) -> Vec<(f32, f32)> {
+    (y_start..y_start + 8).map(|y| (x, y as f32 + 0.5)).collect()
+}
+
+#[test]
+fn test_expansion_location() {
+    let minerals = mineral_line(24.0, 26);
+    let geysers = vec![(30.5, 37.5)];
+    let map = resource_map(&minerals, &geysers);
+
+    let expansions = map.expansion_locations(&minerals, &geysers);
+    assert_eq!(expansions.len(), 1);
+    // 3 free cells between the town hall and both the mineral line and the geyser
+    assert_eq!(expansions[0].center, (30.5, 30.5));
+    assert_eq!(expansions[0].minerals, minerals);
+    assert_eq!(expansions[0].geysers, geysers);
+}
+
+#[test]
+fn test_expansion_groups() {
+    let mut minerals = mineral_line(10.0, 10);
+    minerals.extend(mineral_line(50.0, 40));
+    // A lone mineral field is not a base
+    minerals.push((30.0, 50.5));
+    let map = resource_map(&minerals, &[]);
+
+    let expansions = map.expansion_locations(&minerals, &[]);
+    assert_eq!(expansions.len(), 2);
+    assert_eq!(expansions[0].minerals.len(), 8);
+    assert_eq!(expansions[1].minerals.len(), 8);
+    // Only the side of the mineral lines towards the middle of the map is placeable
+    assert_eq!(expansions[0].center.0, 16.5);
+    assert_eq!(expansions[1].center.0, 43.5);
+}
+
+#[test]
+fn test_expansion_needs_placeable_ground() {
+    let minerals = mineral_line(24.0, 26);
+    let mut map = resource_map(&minerals, &[]);
+    for column in map.points.iter_mut() {
+        for point in column.iter_mut() {
+            point.placeable = false;
+        }
+    }
+
+    assert!(map.expansion_locations(&minerals, &[]).is_empty());
+}
@@ -0,0 +1,64 @@
+use sc2pathlib::path_find::footprint::{Footprint, FOOTPRINT_NAMES};
+use sc2pathlib::path_find::PathFind;
+mod common;
+
+#[test]
+fn test_named_footprints() {
+    for name in FOOTPRINT_NAMES {
+        let footprint = Footprint::named(name).unwrap();
+        assert!(!footprint.placement.is_empty(), "{}", name);
+    }
+    assert_eq!(Footprint::named("mothership"), None);
+    assert_eq!(Footprint::named("building_3x3"), Some(Footprint::rectangle((3, 3))));
+
+    let town_hall = Footprint::named("town_hall").unwrap();
+    assert_eq!(town_hall.pathing.len(), 21);
+    assert_eq!(town_hall.placement.len(), 25);
+}
+
+#[test]
+fn test_footprint_matches_block() {
+    let mut blocks = PathFind::new_internal(vec![vec![1; 20]; 20]);
+    let mut footprints = PathFind::new_internal(vec![vec![1; 20]; 20]);
+    let buildings = [((5.5, 5.5), (3, 3)), ((10.0, 10.0), (2, 2)), ((14.0, 4.5), (2, 1)), ((9.7, 15.2), (2, 2))];
+    for (center, size) in &buildings {
+        blocks.create_block(*center, *size);
+        footprints.create_footprint_block(&Footprint::rectangle(*size), *center);
+    }
+    assert_eq!(blocks.map, footprints.map);
+
+    footprints.remove_footprint_block(&Footprint::rectangle((3, 3)), (5.5, 5.5));
+    assert_eq!(footprints.map[5][5], 1);
+}
+
+#[test]
+fn test_map_footprint_block() {
+    let mut map = common::flat_map(40);
+
+    // Corners of a town hall stay pathable but can't be built on
+    let town_hall = Footprint::named("town_hall").unwrap();
+    map.create_footprint_block(&town_hall, (20.5, 20.5));
+    assert_eq!(map.ground_pathing.map[18][18], 1);
+    assert_eq!(map.ground_pathing.map[18][19], 0);
+    assert_eq!(map.ground_pathing.map[22][22], 1);
+    assert_eq!(map.placement[18][18], 0);
+    assert!(!map.can_place((2, 2), (23.0, 23.0)));
+    map.remove_footprint_block(&town_hall, (20.5, 20.5));
+    assert_eq!(map.ground_pathing.map[20][20], 1);
+    assert!(map.can_place((5, 5), (20.5, 20.5)));
+
+    // Lowered supply depots can be walked over
+    let depot = Footprint::named("supply_depot_lowered").unwrap();
+    map.create_footprint_block(&depot, (10.0, 10.0));
+    assert_eq!(map.ground_pathing.map[9][9], 1);
+    assert!(!map.can_place((2, 2), (10.0, 10.0)));
+
+    // The addon sits right of the building, one cell lower
+    let barracks = Footprint::named("production_with_addon").unwrap();
+    map.create_footprint_block(&barracks, (28.5, 28.5));
+    assert_eq!(map.ground_pathing.map[29][29], 0);
+    assert_eq!(map.ground_pathing.map[30][27], 0);
+    assert_eq!(map.ground_pathing.map[31][28], 0);
+    assert_eq!(map.ground_pathing.map[30][29], 1);
+    assert_eq!(map.ground_pathing.map[32][27], 1);
+}
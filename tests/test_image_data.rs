@@ -0,0 +1,69 @@
+use common::get_automaton_map;
+use sc2pathlib::image_data::ImageData;
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::npy::load_grid;
+use sc2pathlib::Error;
+mod common;
+
+// Packs a grid[x][y] the way the game sends it, rows from y = 0 with the most significant bit first
+fn pack_bits(grid: &[Vec<usize>]) -> Vec<u8> {
+    let width = grid.len();
+    let mut data = vec![0u8; (width * grid[0].len() - 1) / 8 + 1];
+    for (x, column) in grid.iter().enumerate() {
+        for (y, value) in column.iter().enumerate() {
+            if *value > 0 {
+                let index = y * width + x;
+                data[index / 8] |= 0x80 >> (index % 8);
+            }
+        }
+    }
+    data
+}
+
+fn pack_bytes(grid: &[Vec<usize>]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for y in 0..grid[0].len() {
+        for column in grid {
+            data.push(column[y] as u8);
+        }
+    }
+    data
+}
+
+#[test]
+fn test_decode_bits() {
+    // Rows 101 and 011
+    let data = [0b1010_1100];
+    let grid = ImageData::new(&data, 1, 3, 2).decode().unwrap();
+    assert_eq!(grid, vec![vec![1, 0], vec![0, 1], vec![1, 1]]);
+
+    assert_eq!(ImageData::new(&data, 4, 3, 2).decode(), Err(Error::UnsupportedBitsPerPixel(4)));
+    assert_eq!(ImageData::new(&data, 8, 3, 2).decode(),
+               Err(Error::ImageDataSize { expected: 6, found: 1 }));
+}
+
+#[test]
+fn test_map_from_image_data() {
+    let pathing = load_grid("tests/AutomatonLE_pathing.npy").unwrap();
+    let placement = load_grid("tests/AutomatonLE_placement.npy").unwrap();
+    let height = load_grid("tests/AutomatonLE_height.npy").unwrap();
+    let (width, map_height) = (pathing.len(), pathing[0].len());
+
+    let pathing_data = pack_bits(&pathing);
+    let placement_data = pack_bits(&placement);
+    let height_data = pack_bytes(&height);
+    let pathing_image = ImageData::new(&pathing_data, 1, width, map_height);
+    assert_eq!(pathing_image.decode_mask().unwrap(), pathing);
+
+    let map = Map::from_image_data(&pathing_image,
+                                   &ImageData::new(&placement_data, 1, width, map_height),
+                                   &ImageData::new(&height_data, 8, width, map_height),
+                                   18,
+                                   16,
+                                   166,
+                                   164).unwrap();
+    let expected = get_automaton_map();
+    assert_eq!(map.input_hash, expected.input_hash);
+    assert_eq!(map.overlord_spots, expected.overlord_spots);
+    assert_eq!(map.chokes.len(), expected.chokes.len());
+}
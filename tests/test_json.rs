@@ -0,0 +1,67 @@
+use common::get_automaton_map;
+use sc2pathlib::mapping::map_point::Cliff;
+use sc2pathlib::mapping::MapAnalysis;
+mod common;
+
+#[test]
+fn test_analysis_json_round_trip() {
+    let map = get_automaton_map();
+    let analysis = map.analysis(false);
+    assert_eq!(analysis.chokes.len(), map.chokes.len());
+    assert!(analysis.points.is_none());
+
+    let json = analysis.to_json().unwrap();
+    assert!(!json.contains("\"points\""));
+    let loaded = MapAnalysis::from_json(&json).unwrap();
+    assert_eq!(loaded.input_hash, format!("{:016x}", map.input_hash));
+    assert_eq!(loaded.overlord_spots, map.overlord_spots);
+    assert_eq!(loaded.border_cells, map.get_borders().len());
+    assert_eq!(loaded.regions.len(), map.regions.len());
+    assert_eq!(loaded.regions[0].chokes, map.regions[0].chokes);
+    for (loaded, choke) in loaded.chokes.iter().zip(&map.chokes) {
+        assert_eq!(loaded.main_line, choke.main_line);
+        assert_eq!(loaded.pixels, choke.pixels);
+        assert_eq!(loaded.min_length, choke.min_length);
+    }
+}
+
+#[test]
+fn test_analysis_json_points() {
+    let map = get_automaton_map();
+    let json = map.analysis(true).to_json().unwrap();
+    assert!(json.contains("\"cliff_type\":\"high\""));
+
+    let points = MapAnalysis::from_json(&json).unwrap().points.unwrap();
+    assert_eq!((points.len(), points[0].len()), (184, 192));
+    for (column, expected) in points.iter().zip(&map.points) {
+        for (point, expected) in column.iter().zip(expected) {
+            assert_eq!(point.height, expected.height);
+            assert_eq!(point.cliff_type, expected.cliff_type);
+            assert_eq!(point.is_choke, expected.is_choke);
+            assert_eq!(point.overlord_spot, expected.overlord_spot);
+        }
+    }
+
+    assert!(points.iter().flatten().any(|point| point.cliff_type == Cliff::High));
+    assert!(MapAnalysis::from_json("{\"width\": 1}").is_err());
+
+    // Points written before placement was added
+    let old_json = json.replace("\"placeable\":true,", "").replace("\"placeable\":false,", "");
+    assert!(!old_json.contains("\"placeable\""));
+    let points = MapAnalysis::from_json(&old_json).unwrap().points.unwrap();
+    assert!(points.iter().flatten().all(|point| !point.placeable));
+}
+
+#[test]
+fn test_analysis_json_without_regions() {
+    let map = get_automaton_map();
+    let mut value: serde_json::Value = serde_json::from_str(&map.analysis(false).to_json().unwrap()).unwrap();
+    // Analysis written before regions and ramps were added
+    value.as_object_mut().unwrap().remove("regions");
+    value.as_object_mut().unwrap().remove("ramps");
+
+    let loaded = MapAnalysis::from_json(&value.to_string()).unwrap();
+    assert!(loaded.regions.is_empty());
+    assert!(loaded.ramps.is_empty());
+    assert_eq!(loaded.chokes.len(), map.chokes.len());
+}
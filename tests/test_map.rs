@@ -1,5 +1,7 @@
-use common::{get_choke_map, read_vec_from_file};
+use common::{get_automaton_map, get_choke_map, read_vec_from_file};
 use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::MapType;
+use sc2pathlib::Error;
 mod common;
 
 #[test]
@@ -7,12 +9,30 @@ fn test_find_path_map() {
     let grid = read_vec_from_file("tests/maze4x4.txt");
     let grid2 = read_vec_from_file("tests/maze4x4.txt");
     let grid3 = read_vec_from_file("tests/maze4x4.txt");
-    let map = Map::new(grid, grid2, grid3, 1, 1, 3, 3);
-    let r = map.find_path(0, (0f32, 0f32), (3f32, 3f32), Some(0));
+    let map = Map::new(grid, grid2, grid3, 1, 1, 3, 3).unwrap();
+    let r = map.find_path(MapType::Ground, (0f32, 0f32), (3f32, 3f32), Some(0));
     let (_, distance) = r;
     assert_eq!(distance, 6.0);
 }
 
+#[test]
+fn test_map_new_rejects_invalid_input() {
+    let grid = read_vec_from_file("tests/maze4x4.txt");
+    let result = Map::new(grid.clone(), grid.clone(), grid.clone(), 0, 1, 3, 3);
+    assert_eq!(result.err(), Some(Error::InvalidPlayableArea { start: (0, 1), end: (3, 3) }));
+
+    let result = Map::new(grid.clone(), grid.clone(), grid.clone(), 1, 1, 4, 3);
+    assert!(matches!(result, Err(Error::InvalidPlayableArea { .. })));
+
+    let mut short = grid.clone();
+    short.pop();
+    let result = Map::new(grid.clone(), short, grid.clone(), 1, 1, 3, 3);
+    assert!(matches!(result, Err(Error::GridSizeMismatch { .. })));
+
+    let result = Map::new(Vec::new(), Vec::new(), Vec::new(), 1, 1, 3, 3);
+    assert_eq!(result.err(), Some(Error::EmptyGrid));
+}
+
 // Test not working, ignored for now.
 // #[test]
 fn test_find_map_borders() {
@@ -25,6 +45,72 @@ fn test_find_map_borders() {
 // #[test]
 fn test_find_map_chokes() {
     let map = get_choke_map();
-    let r = map.get_chokes();
+    let r = &map.chokes;
     assert_eq!(r.len(), 1);
 }
+
+#[test]
+fn test_map_cache_round_trip() {
+    let map = get_choke_map();
+    let loaded = Map::from_bytes(&map.to_bytes()).unwrap();
+
+    assert_eq!(loaded.input_hash, map.input_hash);
+    assert_eq!(loaded.ground_pathing.map, map.ground_pathing.map);
+    assert_eq!(loaded.reaper_pathing.map, map.reaper_pathing.map);
+    assert_eq!(loaded.get_borders(), map.get_borders());
+    assert_eq!(loaded.chokes.len(), map.chokes.len());
+    assert_eq!(loaded.overlord_spots, map.overlord_spots);
+    assert_eq!(loaded.to_bytes(), map.to_bytes());
+}
+
+#[test]
+fn test_map_cache_rejects_stale() {
+    let map = get_choke_map();
+    let path = std::env::temp_dir().join("sc2pathlib_test_map_cache.bin");
+    let path = path.to_str().unwrap();
+    map.save(path).unwrap();
+
+    assert!(Map::load(path, map.input_hash).is_ok());
+    assert!(Map::load(path, map.input_hash ^ 1).is_err());
+
+    let mut data = map.to_bytes();
+    data.truncate(data.len() / 2);
+    assert!(Map::from_bytes(&data).is_err());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_map_bytes_keep_influence_settings() {
+    let mut map = get_choke_map();
+    map.influence_colossus_map = true;
+    map.influence_reaper_map = true;
+    map.ground_pathing.normalize_influence(20);
+    map.ground_pathing.set_auto_correct(false);
+    map.create_block((20.5, 20.5), (2, 2));
+
+    let loaded = Map::from_bytes(&map.to_bytes()).unwrap();
+    assert!(loaded.influence_colossus_map);
+    assert!(loaded.influence_reaper_map);
+    assert_eq!(loaded.ground_pathing.normal_influence(), 20);
+    assert!(!loaded.ground_pathing.auto_correct());
+    assert_eq!(loaded.ground_pathing.map, map.ground_pathing.map);
+    assert_eq!(loaded.ground_pathing.original_map(), map.ground_pathing.original_map());
+}
+
+#[test]
+fn test_map_concurrent_queries() {
+    let map = std::sync::Arc::new(get_automaton_map());
+    let expected = map.find_path(MapType::Ground, (32.0, 51.0), (150.0, 118.0), Some(0));
+
+    let handles: Vec<_> = (0..4).map(|_| {
+                                    let map = map.clone();
+                                    std::thread::spawn(move || {
+                                        map.find_path(MapType::Ground, (32.0, 51.0), (150.0, 118.0), Some(0))
+                                    })
+                                })
+                                .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
@@ -0,0 +1,58 @@
+use common::get_automaton_map;
+use sc2pathlib::npy::{load_grid, parse_grid};
+mod common;
+
+fn npy_bytes(descr: &str, fortran_order: bool, shape: (usize, usize), data: &[u8]) -> Vec<u8> {
+    let order = if fortran_order { "True" } else { "False" };
+    let mut header = format!("{{'descr': '{}', 'fortran_order': {}, 'shape': ({}, {}), }}",
+                             descr, order, shape.0, shape.1);
+    while (header.len() + 11) % 64 != 0 {
+        header.push(' ');
+    }
+    header.push('\n');
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_npy_orientation() {
+    // array[y][x] with 2 rows and 3 columns
+    let c_order = parse_grid(&npy_bytes("|u1", false, (2, 3), &[1, 2, 3, 4, 5, 6])).unwrap();
+    assert_eq!(c_order, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+
+    let fortran_order = parse_grid(&npy_bytes("|u1", true, (2, 3), &[1, 4, 2, 5, 3, 6])).unwrap();
+    assert_eq!(fortran_order, c_order);
+}
+
+#[test]
+fn test_npy_dtypes() {
+    let bools = parse_grid(&npy_bytes("|b1", false, (1, 2), &[0, 1])).unwrap();
+    assert_eq!(bools, vec![vec![0], vec![1]]);
+
+    let signed = parse_grid(&npy_bytes("<i2", false, (1, 2), &[0xff, 0xff, 0x2c, 0x01])).unwrap();
+    assert_eq!(signed, vec![vec![0], vec![300]]);
+
+    let big_endian = parse_grid(&npy_bytes(">u4", false, (1, 1), &[0, 0, 1, 2])).unwrap();
+    assert_eq!(big_endian, vec![vec![258]]);
+
+    assert!(parse_grid(&npy_bytes("<f8", false, (1, 1), &[0; 8])).is_err());
+    assert!(parse_grid(&npy_bytes("|u1", false, (2, 2), &[1, 2, 3])).is_err());
+}
+
+#[test]
+fn test_npy_game_grids() {
+    let pathing = load_grid("tests/AutomatonLE_pathing.npy").unwrap();
+    let height = load_grid("tests/AutomatonLE_height.npy").unwrap();
+    assert_eq!((pathing.len(), pathing[0].len()), (184, 192));
+    assert_eq!((height.len(), height[0].len()), (184, 192));
+
+    let map = get_automaton_map();
+    let (path, _) = map.ground_pathing.find_path((32, 51), (150, 118), Some(0));
+    assert!(!path.is_empty());
+    assert!(!map.chokes.is_empty());
+    assert!(!map.overlord_spots.is_empty());
+}
@@ -0,0 +1,125 @@
+use sc2pathlib::path_find::footprint::Footprint;
+use sc2pathlib::path_find::PathFind;
+mod common;
+
+#[test]
+fn test_overlapping_obstacles() {
+    let mut grid = vec![vec![1; 20]; 20];
+    grid[6][6] = 0;
+    let mut path_find = PathFind::new_internal(grid);
+    path_find.normalize_influence(10);
+
+    path_find.add_obstacle(1, Footprint::rectangle((3, 3)), (6.5, 6.5));
+    path_find.add_obstacle(2, Footprint::rectangle((2, 2)), (8.0, 8.0));
+    assert_eq!(path_find.map[7][7], 0);
+    assert!(path_find.is_obstacle((8, 8)));
+
+    // The overlap stays blocked until both are gone
+    assert_eq!(path_find.remove_obstacle(1).map(|obstacle| obstacle.id), Some(1));
+    assert_eq!(path_find.map[7][7], 0);
+    assert_eq!(path_find.map[5][5], 10);
+    assert_eq!(path_find.map[6][6], 0);
+    assert_eq!(path_find.obstacles().map(|obstacle| obstacle.id).collect::<Vec<u64>>(), vec![2]);
+
+    assert!(path_find.remove_obstacle(2).is_some());
+    assert!(path_find.remove_obstacle(2).is_none());
+    assert_eq!(path_find.map[7][7], 10);
+    assert!(!path_find.is_obstacle((8, 8)));
+}
+
+#[test]
+fn test_obstacles_survive_reset_and_readd() {
+    let mut path_find = PathFind::new_internal(vec![vec![1; 20]; 20]);
+    path_find.add_obstacle(5, Footprint::rectangle((2, 2)), (4.0, 4.0));
+    path_find.reset();
+    assert_eq!(path_find.map[3][3], 0);
+
+    // Adding the same id again moves the obstacle
+    path_find.add_obstacle(5, Footprint::rectangle((2, 2)), (10.0, 10.0));
+    assert_eq!(path_find.map[3][3], 1);
+    assert_eq!(path_find.map[9][9], 0);
+    assert_eq!(path_find.obstacles().count(), 1);
+
+    let restored = PathFind::from_bytes(&path_find.to_bytes()).unwrap();
+    assert_eq!(restored.obstacles().collect::<Vec<_>>(), path_find.obstacles().collect::<Vec<_>>());
+    assert!(restored.is_obstacle((9, 9)));
+}
+
+#[test]
+fn test_remove_block_keeps_terrain_and_obstacles() {
+    let mut grid = vec![vec![1; 20]; 20];
+    grid[4][4] = 0;
+    let mut path_find = PathFind::new_internal(grid);
+    path_find.normalize_influence(10);
+    path_find.add_obstacle(1, Footprint::rectangle((2, 2)), (8.0, 8.0));
+
+    path_find.create_block((6.0, 6.0), (6, 6));
+    path_find.remove_block((6.0, 6.0), (6, 6));
+    assert_eq!(path_find.map[4][4], 0);
+    assert_eq!(path_find.map[7][7], 0);
+    assert_eq!(path_find.map[5][5], 10);
+
+    path_find.create_footprint_block(&Footprint::rectangle((6, 6)), (6.0, 6.0));
+    path_find.remove_footprint_block(&Footprint::rectangle((6, 6)), (6.0, 6.0));
+    assert_eq!(path_find.map[4][4], 0);
+    assert_eq!(path_find.map[7][7], 0);
+    assert_eq!(path_find.map[5][5], 10);
+}
+
+#[test]
+fn test_map_obstacles() {
+    let mut map = common::flat_map(40);
+
+    let depot = Footprint::named("supply_depot_lowered").unwrap();
+    map.add_obstacle(1, Footprint::rectangle((3, 3)), (20.5, 20.5));
+    map.add_obstacle(2, depot, (22.0, 22.0));
+    assert_eq!(map.placement[21][21], 0);
+
+    map.remove_obstacle(1);
+    assert_eq!(map.ground_pathing.map[21][21], 1);
+    assert_eq!(map.placement[21][21], 0);
+    assert_eq!(map.placement[19][19], 1);
+
+    map.reset();
+    assert_eq!(map.placement[22][22], 0);
+    map.remove_obstacle(2);
+    assert!(map.can_place((3, 3), (21.5, 21.5)));
+
+    // Obstacles on the edge of the playable area don't make the outside pathable
+    map.add_obstacle(3, Footprint::rectangle((2, 2)), (4.0, 4.0));
+    map.remove_obstacle(3);
+    assert_eq!(map.ground_pathing.map[3][3], 0);
+    assert_eq!(map.ground_pathing.map[4][4], 1);
+    assert_eq!(map.placement[3][3], 0);
+    assert!(map.obstacles().next().is_none());
+}
+
+#[test]
+fn test_map_block_over_obstacle() {
+    let mut map = common::flat_map(40);
+    map.add_obstacle(1, Footprint::rectangle((2, 2)), (21.0, 21.0));
+
+    // Removing a block that overlaps the obstacle leaves the obstacle blocked
+    map.create_block((20.0, 20.0), (4, 4));
+    map.remove_block((20.0, 20.0), (4, 4));
+    assert_eq!(map.ground_pathing.map[20][20], 0);
+    assert_eq!(map.reaper_pathing.map[20][20], 0);
+    assert_eq!(map.placement[20][20], 0);
+    assert_eq!(map.placement[18][18], 1);
+
+    map.create_blocks(&[(20.0, 20.0)], (4, 4));
+    map.remove_blocks(&[(20.0, 20.0)], (4, 4));
+    assert_eq!(map.placement[20][20], 0);
+    assert_eq!(map.ground_pathing.map[20][20], 0);
+
+    let footprint = Footprint::rectangle((4, 4));
+    map.create_footprint_block(&footprint, (20.0, 20.0));
+    map.remove_footprint_block(&footprint, (20.0, 20.0));
+    assert_eq!(map.placement[20][20], 0);
+    assert_eq!(map.ground_pathing.map[20][20], 0);
+    assert_eq!(map.placement[18][18], 1);
+
+    map.remove_obstacle(1);
+    assert_eq!(map.placement[20][20], 1);
+    assert_eq!(map.ground_pathing.map[20][20], 1);
+}
@@ -1,4 +1,5 @@
 use common::get_pathfind;
+use sc2pathlib::path_find::PathFind;
 
 mod common;
 
@@ -25,4 +26,38 @@ fn test_find_path_10x10() {
     let r = path_find.find_path((0, 0), (8, 9), Some(0));
     let (_, distance) = r;
     assert_eq!(distance, 12.3136);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_path_find_bytes_round_trip() {
+    let mut path_find = get_pathfind("tests/AutomatonLE.txt");
+    path_find.normalize_influence(10);
+    path_find.set_auto_correct(false);
+    path_find.add_influence(&[(60, 60)], 100.0, 6.0);
+
+    let loaded = PathFind::from_bytes(&path_find.to_bytes()).unwrap();
+    assert_eq!(loaded.map, path_find.map);
+    assert_eq!(loaded.original_map(), path_find.original_map());
+    assert_eq!(loaded.normal_influence(), 10);
+    assert!(!loaded.auto_correct());
+    assert_eq!(loaded.find_path((32, 51), (150, 118), Some(0)),
+               path_find.find_path((32, 51), (150, 118), Some(0)));
+
+    let mut data = path_find.to_bytes();
+    data.push(0);
+    assert!(PathFind::from_bytes(&data).is_err());
+    assert!(PathFind::from_bytes(b"SC2PMAP\0").is_err());
+}
+
+#[test]
+fn test_path_find_bytes_reject_grid_size() {
+    // Magic and version
+    let header = get_pathfind("tests/maze4x4.txt").to_bytes()[..12].to_vec();
+
+    // Huge width with height 0, and the other way around
+    for size in &[[0xff, 0xff, 0xff, 0xff, 0x0f, 0x00], [0x00, 0xff, 0xff, 0xff, 0xff, 0x0f]] {
+        let mut data = header.clone();
+        data.extend_from_slice(size);
+        assert!(PathFind::from_bytes(&data).is_err());
+    }
+}
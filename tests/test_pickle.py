@@ -0,0 +1,47 @@
+# Pickle round trip of PathFind and Map, run against the built extension module:
+#   cargo build --release --features python
+#   mkdir -p target/python && cp target/release/libsc2pathlib.so target/python/sc2pathlib.so
+#   PYTHONPATH=target/python python tests/test_pickle.py
+import pickle
+from typing import List
+
+import sc2pathlib
+
+
+def read_maze(file_name: str) -> List[List[int]]:
+    with open(file_name, "r") as text:
+        lines = text.read().split("\n")
+    return [[int(lines[x][y]) for x in range(len(lines))] for y in range(len(lines[0]))]
+
+
+def test_path_find():
+    path_find = sc2pathlib.PathFind(read_maze("tests/AutomatonLE.txt"))
+    path_find.normalize_influence(10)
+    path_find.add_influence([(60, 60)], 100.0, 6.0)
+
+    loaded = pickle.loads(pickle.dumps(path_find))
+    assert isinstance(loaded, sc2pathlib.PathFind)
+    assert (loaded.width, loaded.height) == (path_find.width, path_find.height)
+    assert loaded.normal_influence == 10
+    assert loaded.find_path_influence((32, 51), (150, 118)) == path_find.find_path_influence((32, 51), (150, 118))
+
+
+def test_map():
+    # Two plateaus separated by a cliff with a gap, playable area inset by 4
+    grid = [[int(4 <= x <= 35 and 4 <= y <= 35 and (x != 20 or 18 <= y <= 21)) for y in range(40)] for x in range(40)]
+    heights = [[191 if x < 20 else 207] * 40 for x in range(40)]
+    game_map = sc2pathlib.Map(grid, grid, heights, 4, 4, 35, 35)
+    game_map.create_block((10.5, 10.5), (2, 2))
+
+    loaded = pickle.loads(pickle.dumps(game_map))
+    assert isinstance(loaded, sc2pathlib.Map)
+    assert len(loaded.chokes) == len(game_map.chokes)
+    assert loaded.overlord_spots == game_map.overlord_spots
+    for start, end in [((6.0, 6.0), (30.0, 30.0)), ((10.0, 8.0), (10.0, 13.0))]:
+        assert loaded.find_path(0, start, end) == game_map.find_path(0, start, end)
+
+
+if __name__ == "__main__":
+    test_path_find()
+    test_map()
+    print("Pickle tests passed")
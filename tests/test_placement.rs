@@ -0,0 +1,94 @@
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::PlacementOptions;
+mod common;
+
+/// Flat 40x40 map, placeable inside the playable area except for a 2x2 rock at (10, 10).
+fn placement_map() -> Map {
+    let grid = common::flat_grid(40);
+    let mut placement = grid.clone();
+    for column in &mut placement[10..12] {
+        for cell in &mut column[10..12] {
+            *cell = 0;
+        }
+    }
+
+    Map::new(grid, placement, vec![vec![10; 40]; 40], 4, 4, 35, 35).unwrap()
+}
+
+#[test]
+fn test_placement_follows_blocks() {
+    let mut map = placement_map();
+    assert!(map.can_place((3, 3), (20.5, 20.5)));
+    assert!(!map.can_place((2, 2), (11.0, 11.0)));
+    assert!(!map.can_place((5, 5), (2.5, 2.5)));
+
+    map.create_block((20.5, 20.5), (3, 3));
+    assert!(!map.can_place((3, 3), (20.5, 20.5)));
+    assert!(!map.can_place((2, 2), (22.0, 22.0)));
+    assert!(map.can_place((2, 2), (23.0, 23.0)));
+
+    let restored = Map::from_bytes(&map.to_bytes()).unwrap();
+    assert!(!restored.can_place((3, 3), (20.5, 20.5)));
+
+    map.remove_block((20.5, 20.5), (3, 3));
+    assert!(map.can_place((3, 3), (20.5, 20.5)));
+
+    // Removing a block doesn't make cells placeable that never were
+    map.create_block((11.0, 11.0), (2, 2));
+    map.remove_block((11.0, 11.0), (2, 2));
+    assert!(!map.can_place((2, 2), (11.0, 11.0)));
+
+    map.create_block((20.5, 20.5), (3, 3));
+    map.reset();
+    assert!(map.can_place((3, 3), (20.5, 20.5)));
+}
+
+#[test]
+fn test_find_placement_near() {
+    let mut map = placement_map();
+    let options = PlacementOptions::default();
+    assert_eq!(map.find_placement_near((3, 3), (20.5, 20.5), 5.0, &options), Some((20.5, 20.5)));
+    // Grid aligned position closest to an unaligned center
+    assert_eq!(map.find_placement_near((2, 2), (20.2, 20.2), 5.0, &options), Some((20.0, 20.0)));
+
+    map.create_block((20.5, 20.5), (3, 3));
+    let position = map.find_placement_near((3, 3), (20.5, 20.5), 5.0, &options).unwrap();
+    assert_eq!((position.0 - 20.5).abs().max((position.1 - 20.5).abs()), 3.0);
+    assert!(map.can_place((3, 3), position));
+
+    // With a walkway the building can't be next to the block
+    let walkway = PlacementOptions { walkway: true,
+                                     ..PlacementOptions::default() };
+    let position = map.find_placement_near((3, 3), (20.5, 20.5), 5.0, &walkway).unwrap();
+    assert_eq!((position.0 - 20.5).abs().max((position.1 - 20.5).abs()), 4.0);
+
+    assert_eq!(map.find_placement_near((3, 3), (20.5, 20.5), 1.0, &options), None);
+}
+
+#[test]
+fn test_find_placements() {
+    let map = placement_map();
+    let positions = map.find_placements((2, 2), (20.0, 20.0), 6.0, 4, &PlacementOptions::default());
+    assert_eq!(positions.len(), 4);
+    assert_eq!(positions[0], (20.0, 20.0));
+    for (i, first) in positions.iter().enumerate() {
+        for second in &positions[i + 1..] {
+            assert!((first.0 - second.0).abs() >= 2.0 || (first.1 - second.1).abs() >= 2.0);
+        }
+    }
+}
+
+#[test]
+fn test_placement_avoids_mineral_line() {
+    let minerals: Vec<(f32, f32)> = (16..24).map(|y| (12.0, y as f32 + 0.5)).collect();
+    let map = placement_map();
+    let expansions = map.expansion_locations(&minerals, &[]);
+    assert_eq!(expansions[0].center, (18.5, 20.5));
+
+    let options = PlacementOptions { mineral_lines: expansions,
+                                     ..PlacementOptions::default() };
+    // Between the town hall and the minerals
+    assert!(map.can_place((2, 2), (14.0, 20.0)));
+    // Closest free spot is behind the minerals
+    assert_eq!(map.find_placement_near((2, 2), (14.0, 20.0), 10.0, &options), Some((10.0, 20.0)));
+}
@@ -0,0 +1,45 @@
+use common::get_automaton_map;
+use sc2pathlib::mapping::map::Map;
+mod common;
+
+#[test]
+fn test_ramps() {
+    let map = get_automaton_map();
+    // Unbuildable flat patches in the middle of the map are not ramps
+    assert_eq!(map.ramps.len(), 22);
+
+    for ramp in &map.ramps {
+        assert!(ramp.upper_height >= ramp.lower_height + 16);
+        assert!(!ramp.upper.is_empty() && !ramp.lower.is_empty());
+        assert!(ramp.upper_region.is_some() && ramp.lower_region.is_some());
+        assert!((ramp.direction.0.hypot(ramp.direction.1) - 1.0).abs() < 1e-3);
+        for &(x, y) in &ramp.cells {
+            assert!(map.points[x][y].walkable && !map.points[x][y].placeable);
+        }
+    }
+
+    let restored = Map::from_bytes(&map.to_bytes()).unwrap();
+    assert_eq!(restored.ramps.len(), map.ramps.len());
+}
+
+#[test]
+fn test_main_ramp() {
+    let map = get_automaton_map();
+    let start = (29.5, 65.5);
+    let other_start = (154.5, 114.5);
+
+    let ramp = map.main_ramp(start).unwrap();
+    let other_ramp = map.main_ramp(other_start).unwrap();
+    assert_ne!(ramp.index, other_ramp.index);
+    assert_eq!(ramp.upper_region, map.zone_at(start));
+    assert_eq!(ramp.upper_height, map.region_at(start).unwrap().height);
+    assert_eq!(ramp.cells.len(), other_ramp.cells.len());
+    assert!((ramp.width - other_ramp.width).abs() < 0.1);
+
+    // Ramp leads down and away from the start location
+    let away = (ramp.top_center.0 - start.0, ramp.top_center.1 - start.1);
+    assert!(away.0 * ramp.direction.0 + away.1 * ramp.direction.1 > 0.0);
+    // The main ramp is the choke out of the main
+    let main = map.region_at(start).unwrap();
+    assert_eq!(map.region_graph.neighbours(main.index), vec![(ramp.lower_region.unwrap(), main.chokes[0])]);
+}
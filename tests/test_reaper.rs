@@ -0,0 +1,74 @@
+use sc2pathlib::mapping::mappings::MapType;
+mod common;
+
+#[test]
+fn test_reaper_jumps() {
+    let map = common::get_automaton_map();
+    let jumps = map.reaper_jumps();
+    assert!(!jumps.is_empty());
+
+    for jump in jumps.iter() {
+        assert!(map.points[jump.takeoff.0][jump.takeoff.1].walkable);
+        assert!(map.points[jump.landing.0][jump.landing.1].walkable);
+        let takeoff_level = map.terrain_level((jump.takeoff.0 as f32, jump.takeoff.1 as f32)).unwrap();
+        let landing_level = map.terrain_level((jump.landing.0 as f32, jump.landing.1 as f32)).unwrap();
+        assert_eq!(jump.up, landing_level > takeoff_level);
+        // Every jump can be made back
+        let back = jumps.iter().find(|back| back.takeoff == jump.landing && back.landing == jump.takeoff);
+        assert_eq!(back.map(|back| back.up), Some(!jump.up));
+    }
+}
+
+#[test]
+fn test_reaper_jumps_blocked() {
+    let mut map = common::get_automaton_map();
+    let count = map.reaper_jumps().len();
+    assert!(map.reaper_jumps().iter().any(|jump| jump.takeoff == (47, 50)));
+
+    map.create_block((47.5, 50.5), (1, 1));
+    assert!(!map.reaper_jumps().iter().any(|jump| jump.takeoff == (47, 50) || jump.landing == (47, 50)));
+    assert!(map.reaper_jumps().len() < count);
+    let (path, _, _) = map.find_path_reaper((46.5, 48.5), (46.5, 52.5), 2.0);
+    assert!(!path.contains(&(47, 50)));
+
+    map.remove_block((47.5, 50.5), (1, 1));
+    assert_eq!(map.reaper_jumps().len(), count);
+}
+
+#[test]
+fn test_find_path_reaper() {
+    let mut map = common::get_automaton_map();
+    // Below and above the cliff of the main, by the natural
+    let low = (46.5, 48.5);
+    let high = (46.5, 52.5);
+
+    let (path, distance, jumps) = map.find_path_reaper(low, high, 2.0);
+    let (_, ground_distance) = map.find_path(MapType::Ground, low, high, Some(1));
+    assert_eq!(path, vec![(47, 49), (47, 50), (47, 53)]);
+    assert!((distance - 3.0).abs() < 0.01);
+    assert_eq!(jumps.len(), 1);
+    assert!(jumps[0].up);
+    assert_eq!((jumps[0].takeoff, jumps[0].landing), ((47, 50), (47, 53)));
+
+    let (_, _, jumps) = map.find_path_reaper(high, low, 2.0);
+    assert!(!jumps[0].up);
+
+    // Jumps that take long enough aren't worth it
+    let (_, slow_distance, slow_jumps) = map.find_path_reaper(low, high, 100.0);
+    assert!(slow_jumps.is_empty());
+    assert!((slow_distance - ground_distance).abs() < 0.01);
+
+    // Reapers can jump up right away from the start
+    let (jump, escape_distance) = map.reaper_escape_jump(low, 0).unwrap();
+    assert_eq!(jump.takeoff, (47, 49));
+    assert!(jump.up);
+    assert_eq!(escape_distance, 0.0);
+
+    // Landings near the closest one are covered, so escape elsewhere
+    let covered = jump.landing;
+    map.reaper_pathing.add_influence_flat(&[covered], 50.0, 3.0);
+    let (jump, further) = map.reaper_escape_jump(low, 0).unwrap();
+    assert_eq!(map.reaper_pathing.map[jump.landing.0][jump.landing.1], map.reaper_pathing.normal_influence());
+    assert!(further > escape_distance);
+    assert_eq!(map.reaper_escape_jump(low, 50).unwrap().0.landing, covered);
+}
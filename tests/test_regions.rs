@@ -0,0 +1,148 @@
+use common::get_automaton_map;
+use sc2pathlib::mapping::map::Map;
+use sc2pathlib::mapping::region_graph::RegionGraph;
+mod common;
+
+#[test]
+fn test_regions_cover_walkable_area() {
+    let map = get_automaton_map();
+    assert_eq!(map.regions.len(), 16);
+
+    let walkable = map.points.iter().flatten().filter(|point| point.walkable && point.pathable).count();
+    let area: usize = map.regions.iter().map(|region| region.area).sum();
+    assert_eq!(area, walkable);
+
+    for (x, column) in map.points.iter().enumerate() {
+        for (y, point) in column.iter().enumerate() {
+            assert_eq!(point.zone_index >= 0, point.walkable && point.pathable);
+            if point.zone_index >= 0 {
+                let region = &map.regions[point.zone_index as usize];
+                let (low, high) = region.bounding_box;
+                assert!(low.0 <= x && x <= high.0 && low.1 <= y && y <= high.1);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_regions_main_bases() {
+    let map = get_automaton_map();
+
+    // Start locations of AutomatonLE are on the highest level with a single ramp out
+    let main = map.region_at((29.5, 65.5)).unwrap();
+    let other_main = map.region_at((154.5, 114.5)).unwrap();
+    assert_eq!(main.height, other_main.height);
+    // Choke cells are split by distance so the mirrored mains can differ by a few cells
+    assert!((main.area as i64 - other_main.area as i64).abs() <= 8);
+    assert_eq!(main.chokes.len(), 1);
+    assert_eq!(other_main.chokes.len(), 1);
+    assert!(map.regions.iter().all(|region| region.height <= main.height));
+
+    // The ramp connects the main to one other region
+    let choke = main.chokes[0];
+    let connected: Vec<usize> = map.regions
+                                   .iter()
+                                   .filter(|region| region.chokes.contains(&choke))
+                                   .map(|region| region.index)
+                                   .collect();
+    assert_eq!(connected.len(), 2);
+    assert!(connected.contains(&main.index));
+
+    assert!(map.region_at((0.0, 0.0)).is_none());
+    assert!(map.zone_at((-1.0, 5.0)).is_none());
+}
+
+#[test]
+fn test_regions_survive_cache() {
+    let map = get_automaton_map();
+    let loaded = Map::from_bytes(&map.to_bytes()).unwrap();
+
+    assert_eq!(loaded.regions.len(), map.regions.len());
+    for (loaded, region) in loaded.regions.iter().zip(&map.regions) {
+        assert_eq!(loaded.area, region.area);
+        assert_eq!(loaded.centroid, region.centroid);
+        assert_eq!(loaded.bounding_box, region.bounding_box);
+        assert_eq!(loaded.chokes, region.chokes);
+    }
+}
+
+#[test]
+fn test_region_distances() {
+    let map = get_automaton_map();
+    let distances = &map.region_graph.distances;
+    assert_eq!(distances.len(), map.regions.len());
+
+    for (a, row) in distances.iter().enumerate() {
+        assert_eq!(row[a], 0.0);
+        for (b, distance) in row.iter().enumerate() {
+            assert!(distance.is_finite());
+            assert!((distance - distances[b][a]).abs() < 0.01);
+        }
+    }
+
+    let main = map.zone_at((29.5, 65.5)).unwrap();
+    let other_main = map.zone_at((154.5, 114.5)).unwrap();
+    let farthest = distances[main].iter().cloned().fold(0.0, f32::max);
+    assert_eq!(distances[main][other_main], farthest);
+    assert_eq!(map.region_distance(main, other_main), Some(farthest));
+    assert_eq!(map.region_distance(main, map.regions.len()), None);
+}
+
+#[test]
+fn test_region_route() {
+    let map = get_automaton_map();
+    let main = map.region_at((29.5, 65.5)).unwrap();
+    let other_main = map.region_at((154.5, 114.5)).unwrap();
+
+    let neighbours = map.region_graph.neighbours(main.index);
+    assert_eq!(neighbours.len(), 1);
+    let (natural, ramp) = neighbours[0];
+    assert_eq!(ramp, main.chokes[0]);
+
+    let route = map.region_route((29.5, 65.5), (154.5, 114.5)).unwrap();
+    assert_eq!(route.regions.first(), Some(&main.index));
+    assert_eq!(route.regions.last(), Some(&other_main.index));
+    assert_eq!(route.regions[1], natural);
+    assert_eq!(route.chokes[0], ramp);
+    assert_eq!(route.chokes.len(), route.regions.len() - 1);
+    assert!((route.distance - map.region_graph.distances[main.index][other_main.index]).abs() < 0.01);
+
+    // Every step goes through a choke between the two regions
+    for (pair, choke) in route.regions.windows(2).zip(&route.chokes) {
+        assert!(map.regions[pair[0]].chokes.contains(choke));
+        assert!(map.regions[pair[1]].chokes.contains(choke));
+    }
+
+    let same = map.region_route((29.5, 65.5), (30.5, 66.5)).unwrap();
+    assert_eq!(same.regions, vec![main.index]);
+    assert!(same.chokes.is_empty());
+    assert!(map.region_route((0.0, 0.0), (29.5, 65.5)).is_none());
+
+    // Consecutive chokes of the route are connected through the region between them
+    let choke_edges = map.region_graph.choke_edges();
+    for pair in route.chokes.windows(2) {
+        let key = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+        assert!(choke_edges.iter().any(|&(first, second, distance)| (first, second) == key && distance > 0.0));
+    }
+
+    let loaded = Map::from_bytes(&map.to_bytes()).unwrap();
+    assert_eq!(loaded.region_graph.distances, map.region_graph.distances);
+    assert_eq!(loaded.region_graph.choke_edges(), choke_edges);
+    assert_eq!(loaded.region_route((29.5, 65.5), (154.5, 114.5)), Some(route));
+}
+
+#[test]
+fn test_region_graph_choke_without_pixels() {
+    let map = get_automaton_map();
+    let main = map.region_at((29.5, 65.5)).unwrap();
+    let ramp = main.chokes[0];
+    let mut chokes = map.chokes.clone();
+    chokes[ramp].pixels.clear();
+    chokes[ramp].main_line = ((-50.0, -50.0), (f32::NAN, 500.0));
+
+    let graph = RegionGraph::new(&map.points, &map.regions, &chokes);
+    assert_eq!(graph.choke_centers[ramp], None);
+    assert!(graph.choke_edges().iter().all(|&(first, second, _)| first != ramp && second != ramp));
+    assert!(graph.route(main.index, map.region_at((154.5, 114.5)).unwrap().index).is_none());
+    assert!(map.region_graph.choke_centers.iter().all(|center| center.is_some()));
+}
@@ -0,0 +1,28 @@
+use common::{get_choke_map, get_pathfind};
+mod common;
+
+#[test]
+fn test_render_pathing_ppm() {
+    let path_find = get_pathfind("tests/maze4x4.txt");
+    let (path, _) = path_find.find_path((0, 0), (3, 3), Some(0));
+    let image = path_find.render(false, &[path]).scaled(2);
+
+    assert_eq!(image.width, 8);
+    assert_eq!(image.height, 8);
+    assert_eq!(image.get(0, 0), sc2pathlib::render::PATH_COLOR);
+
+    let data = image.to_ppm();
+    assert!(data.starts_with(b"P6\n8 8\n255\n"));
+    assert_eq!(data.len(), "P6\n8 8\n255\n".len() + 8 * 8 * 3);
+}
+
+#[test]
+fn test_render_chokes_png() {
+    let map = get_choke_map();
+    let image = map.render_chokes();
+    let data = image.to_png();
+
+    assert_eq!(image.width, map.ground_pathing.width);
+    assert_eq!(&data[..8], &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    assert_eq!(&data[data.len() - 8..data.len() - 4], b"IEND");
+}
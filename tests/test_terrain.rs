@@ -0,0 +1,45 @@
+use sc2pathlib::mapping::terrain::{height_to_level, height_to_world, LEVEL_DIFFERENCE};
+mod common;
+
+#[test]
+fn test_height_conversion() {
+    assert_eq!(height_to_level(191), 12);
+    assert_eq!(height_to_level(191 + LEVEL_DIFFERENCE), 13);
+    assert_eq!(height_to_level(0), 0);
+    assert_eq!(height_to_world(0), -16.0);
+    assert_eq!(height_to_world(255), 16.0);
+    assert!((height_to_world(207) - height_to_world(191) - 2.0).abs() < 0.01);
+}
+
+#[test]
+fn test_high_ground() {
+    let map = common::get_automaton_map();
+    let main = (29.5, 65.5);
+    let natural = (35.0, 38.0);
+
+    let main_level = map.terrain_level(main).unwrap();
+    assert!(main_level > map.terrain_level(natural).unwrap());
+    assert!(map.world_height(main).unwrap() > map.world_height(natural).unwrap());
+    assert!(map.is_high_ground_over(main, natural));
+    assert!(!map.is_high_ground_over(natural, main));
+    assert!(!map.is_high_ground_over(main, main));
+    assert_eq!(map.terrain_level((-1.0, 5.0)), None);
+
+    // Cells above the bottom of the main ramp are in the main
+    let ramp = map.main_ramp(main).unwrap();
+    let bottom = ramp.bottom_center;
+    let overlooking = map.overlooking_cells(bottom, 8.0);
+    assert!(!overlooking.is_empty());
+    for &(x, y) in &overlooking {
+        assert!(map.terrain_level((x as f32, y as f32)).unwrap() > map.terrain_level(bottom).unwrap());
+        assert!((x as f32 + 0.5 - bottom.0).hypot(y as f32 + 0.5 - bottom.1) <= 8.0);
+    }
+    assert!(overlooking.iter().any(|&(x, y)| map.points[x][y].zone_index == ramp.upper_region.unwrap() as i32));
+    assert!(map.overlooking_cells(main, 8.0).is_empty());
+
+    let layer = map.overlooking_layer(8.0);
+    for &(x, y) in &[(bottom.0 as usize, bottom.1 as usize), (29, 65), (35, 38), (0, 0)] {
+        assert_eq!(layer[x][y], map.overlooking_cells((x as f32 + 0.5, y as f32 + 0.5), 8.0).len());
+    }
+    assert!(layer[bottom.0 as usize][bottom.1 as usize] > 0);
+}
@@ -0,0 +1,48 @@
+use sc2pathlib::mapping::tumors::TUMOR_CAST_RANGE;
+mod common;
+
+fn creep_around(center: (f32, f32), radius: f32) -> Vec<Vec<usize>> {
+    (0..60).map(|x| {
+               (0..60).map(|y| ((x as f32 + 0.5 - center.0).hypot(y as f32 + 0.5 - center.1) <= radius) as usize)
+                      .collect()
+           })
+           .collect()
+}
+
+#[test]
+fn test_plan_creep_tumors() {
+    let mut map = common::flat_map(60);
+    let tumor = (15.5, 30.5);
+    map.set_creep(creep_around(tumor, 10.0)).unwrap();
+
+    let plan = map.plan_creep_tumors(&[tumor], (50.0, 30.0), 0);
+    assert_eq!(plan.len(), 1);
+    let (source, position) = plan[0];
+    assert_eq!(source, tumor);
+    // Furthest towards the target that the creep and the cast range allow
+    assert!(position.0 > 23.0, "{:?}", position);
+    assert!((position.1 - 30.5).abs() <= 2.0, "{:?}", position);
+    assert!((position.0 - tumor.0).hypot(position.1 - tumor.1) <= TUMOR_CAST_RANGE + 1.0);
+    assert!(map.has_creep((position.0 as usize, position.1 as usize)));
+
+    // A target that isn't a number doesn't panic
+    map.plan_creep_tumors(&[tumor, (16.5, 31.5)], (f32::NAN, 30.0), 0);
+}
+
+#[test]
+fn test_plan_avoids_influence_and_overlap() {
+    let mut map = common::flat_map(60);
+    let tumors = [(15.5, 30.5), (16.5, 31.5)];
+    map.set_creep(creep_around(tumors[0], 11.0)).unwrap();
+
+    // Two tumors next to each other don't pick the same area
+    let plan = map.plan_creep_tumors(&tumors, (50.0, 30.0), 0);
+    assert_eq!(plan.len(), 2);
+    let (first, second) = (plan[0].1, plan[1].1);
+    assert!((first.0 - second.0).hypot(first.1 - second.1) > 5.0, "{:?}", plan);
+
+    map.ground_pathing.add_influence_flat(&[(24, 30)], 50.0, 4.0);
+    let plan = map.plan_creep_tumors(&tumors[..1], (50.0, 30.0), 0);
+    let position = plan[0].1;
+    assert_eq!(map.ground_pathing.map[position.0 as usize][position.1 as usize], 1);
+}
@@ -0,0 +1,55 @@
+use common::get_automaton_map;
+use sc2pathlib::mapping::map::Map;
+mod common;
+
+const MAIN: (usize, usize) = (29, 65);
+const NATURAL: (usize, usize) = (35, 38);
+
+fn block(map: &mut Map, centers: &[(f32, f32)], sizes: &[(usize, usize)]) {
+    for (center, size) in centers.iter().zip(sizes) {
+        map.create_block(*center, *size);
+    }
+}
+
+#[test]
+fn test_wall_main_ramp() {
+    let mut map = get_automaton_map();
+    let ramp = map.main_ramp((29.5, 65.5)).unwrap().index;
+    let buildings = [(3, 3), (2, 2), (2, 2)];
+    assert!(!map.ground_pathing.find_path(MAIN, NATURAL, None).0.is_empty());
+
+    let wall = map.wall_ramp(ramp, &buildings, 0).unwrap();
+    assert_eq!(wall.len(), 3);
+    // Barracks centers are on cell centers, depots on cell corners
+    assert_eq!(wall[0].0.fract(), 0.5);
+    assert_eq!(wall[1].0.fract(), 0.0);
+
+    block(&mut map, &wall, &buildings);
+    assert!(map.ground_pathing.find_path(MAIN, NATURAL, None).0.is_empty());
+
+    // A single depot can't close the ramp
+    assert_eq!(map.wall_ramp(ramp, &[(2, 2)], 0), None);
+}
+
+#[test]
+fn test_wall_with_gap() {
+    let mut map = get_automaton_map();
+    let ramp = map.main_ramp((29.5, 65.5)).unwrap().index;
+    let buildings = [(3, 3), (3, 3)];
+
+    let wall = map.wall_ramp(ramp, &buildings, 1).unwrap();
+    block(&mut map, &wall, &buildings);
+    assert!(!map.ground_pathing.find_path(MAIN, NATURAL, None).0.is_empty());
+    assert!(map.ground_pathing.find_path_large(MAIN, NATURAL, None).0.is_empty());
+}
+
+#[test]
+fn test_wall_choke() {
+    let mut map = get_automaton_map();
+    let choke = map.region_at((29.5, 65.5)).unwrap().chokes[0];
+    let buildings = [(3, 3), (2, 2), (2, 2)];
+
+    let wall = map.wall_choke(choke, &buildings, 0).unwrap();
+    block(&mut map, &wall, &buildings);
+    assert!(map.ground_pathing.find_path(MAIN, NATURAL, None).0.is_empty());
+}
@@ -0,0 +1,41 @@
+use sc2pathlib::mapping::warp_in::{PYLON_POWER_RADIUS, WARP_PRISM_POWER_RADIUS};
+mod common;
+
+#[test]
+fn test_warp_in_cells() {
+    let mut map = common::flat_map(40);
+    let pylon = (20.0, 20.0);
+    map.create_block(pylon, (2, 2));
+
+    let cells = map.warp_in_cells(&[(pylon, PYLON_POWER_RADIUS)], &[(23.4, 20.6)]);
+    assert!(!cells.contains(&(19, 19)));
+    assert!(!cells.contains(&(23, 20)));
+    assert!(cells.contains(&(24, 20)));
+    assert!(!cells.contains(&(26, 20)));
+    assert_eq!(cells.len(), map.power_field(&[(pylon, PYLON_POWER_RADIUS)]).len() - 5);
+
+    // The prism on the edge of the map only powers the playable area
+    let cells = map.warp_in_cells(&[((5.0, 5.0), WARP_PRISM_POWER_RADIUS)], &[]);
+    assert!(cells.iter().all(|&(x, y)| x >= 4 && y >= 4));
+    assert!(cells.contains(&(4, 4)));
+}
+
+#[test]
+fn test_find_warp_ins() {
+    let mut map = common::flat_map(40);
+    let sources = [((20.0, 20.0), PYLON_POWER_RADIUS)];
+
+    let spots = map.find_warp_ins(&sources, &[], (26.0, 20.0), 4, 2.0);
+    assert_eq!(spots.len(), 4);
+    assert_eq!(spots[0], (25.5, 19.5));
+    for (index, spot) in spots.iter().enumerate() {
+        for other in &spots[index + 1..] {
+            assert!((spot.0 - other.0).hypot(spot.1 - other.1) >= 2.0);
+        }
+    }
+
+    // Spots under fire are avoided even when they are closer to the target
+    map.ground_pathing.add_influence_flat(&[(26, 20)], 50.0, 4.0);
+    let spots = map.find_warp_ins(&sources, &[], (26.0, 20.0), 4, 1.0);
+    assert!(spots.iter().all(|spot| map.ground_pathing.map[spot.0 as usize][spot.1 as usize] == 1), "{:?}", spots);
+}